@@ -3,20 +3,179 @@
 //! Key pieces:
 //! - [`Contig`] describes how a value occupies a range inside an `&[F]` slice and
 //!   exposes read/write views for that range.
+//! - [`ContigStatic`] extends [`Contig`] for types whose footprint is fixed at compile time, so
+//!   they can live in `[F; T::LEN]` stack buffers without a runtime layout.
+//! - [`ContigError`] is returned by the fallible `try_*` APIs (layout overflow, short
+//!   buffers, rejected configs) for callers that must not panic on bad input.
+//! - [`Contig::schema`] reflects any layout as a [`SchemaNode`] tree of named or indexed
+//!   scalar ranges, for debugging, logging, and export; [`Contig::schema_hash`] condenses it
+//!   and the scalar type into a build-independent fingerprint for compatibility checks.
+//! - [`ContigValue`] pairs a type with an owned Rust value (`Vec` for `Dyn`, `Option` for
+//!   `Opt`, ...) and converts between the two with `pack`/`unpack`.
+//! - [`Real`] scalars get whole-view arithmetic (`fill`, `copy_from`, `axpy`, `scale`,
+//!   `dot`, `norm`) on array and derived struct views, built on the slice kernels in [`ops`].
+//! - [`ContigBox`] owns a layout together with its buffer for storing state without
+//!   borrowing between the two.
+//! - [`relayout`] moves data to the layout of a changed config, keeping overlapping fields
+//!   and `Dyn` elements via [`Contig::copy_overlap`]; [`migrate`] does the same across
+//!   changed types by matching field paths, reporting what was added and removed.
 //! - [`TakeCursor`] is a tiny helper for carving non-overlapping ranges while assembling
 //!   a struct layout.
-//! - Ready-made adapters for scalars, dynamic arrays (`Dyn<[T]>`), and (optionally)
-//!   nalgebra vectors/matrices so common building blocks slot into a contiguous buffer without
-//!   boilerplate.
+//! - With the `serde` feature, configs and layouts (including those generated by
+//!   `contig-derive`) implement `Serialize` and `Deserialize`, so configs can be loaded
+//!   from JSON or TOML descriptions.
+//!   Buffer contents serialize by field name through [`SchemaSerialize`] (which array and
+//!   derived views use for their `Serialize` impls) and read back with [`SchemaSeed`], or
+//!   in place through a mutable view with [`DeserializeView::deserialize_from`].
+//! - With the `snapshot` feature, [`snapshot::save`] and [`snapshot::load`] write and read
+//!   versioned checkpoint files (config, schema, raw scalars) that are refused on load when
+//!   the type's schema has changed.
+//! - Ready-made adapters for scalars, fixed-size arrays (`[T; N]`), dynamic arrays
+//!   (`Dyn<[T]>`), ragged arrays with per-element configs (`Ragged<[T]>`), optional values
+//!   (`Opt<T>`), and (optionally) nalgebra vectors/matrices so common building blocks slot
+//!   into a contiguous buffer without boilerplate.
 //!
 //! The `contig-derive` crate emits config/layout/view types that implement [`Contig`], letting
 //! complex user-defined structs share the same zero-copy API as these primitives.
 
-use core::{marker::PhantomData, ops::Range};
+use core::{
+    marker::PhantomData,
+    ops::{Bound, Range, RangeBounds},
+};
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+
+/// Emits the serde items in braces ahead of the item that follows, or the item alone when
+/// this crate's `serde` feature is off. `contig-derive` wraps its output in this macro so the
+/// decision follows the `contig-core` the user crate links, not the proc macro's own build.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_serde {
+    ({ $($serde:tt)* } $($item:tt)*) => {
+        $($serde)*
+        $($item)*
+    };
+}
+
+/// Without the `serde` feature the serde items are dropped; see the enabled variant.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_serde {
+    ({ $($serde:tt)* } $($item:tt)*) => {
+        $($item)*
+    };
+}
+
+// ---------- Errors ----------
+
+/// Failure while computing a layout or borrowing a view through the fallible APIs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContigError {
+    /// The buffer holds fewer scalars than the layout spans.
+    BufferTooShort {
+        /// Scalars required by the layout.
+        expected: usize,
+        /// Scalars available in the buffer.
+        actual: usize,
+    },
+    /// An owned buffer holds more scalars than its layout spans.
+    BufferTooLong {
+        /// Scalars spanned by the layout.
+        expected: usize,
+        /// Scalars held by the buffer.
+        actual: usize,
+    },
+    /// A footprint computation overflowed `usize`.
+    Overflow,
+    /// The configuration cannot describe a valid layout.
+    InvalidConfig {
+        /// Human-readable reason the configuration was rejected.
+        reason: &'static str,
+    },
+    /// A field path is malformed or does not name anything in the layout.
+    InvalidPath {
+        /// The path as given.
+        path: String,
+        /// Human-readable reason the path was rejected.
+        reason: &'static str,
+    },
+    /// The elements of an array value need different configs, which only `Ragged` allows.
+    MixedElements {
+        /// Path of the array within the value, e.g. `links[2].spheres`.
+        path: String,
+        /// First element whose config differs from that of element 0.
+        index: usize,
+    },
+}
+
+impl ContigError {
+    /// Check that a buffer of `actual` scalars covers a layout spanning `expected`.
+    pub fn check_len(expected: usize, actual: usize) -> Result<(), ContigError> {
+        if actual < expected {
+            Err(ContigError::BufferTooShort { expected, actual })
+        } else {
+            Ok(())
+        }
+    }
+    #[doc(hidden)]
+    /// Place the array named by a [`MixedElements`](Self::MixedElements) error under
+    /// `segment`, as seen from the value containing it.
+    pub fn within(self, segment: Segment) -> Self {
+        match self {
+            ContigError::MixedElements { path, index } => {
+                let separator = match path.chars().next() {
+                    Some('[') | None => "",
+                    Some(_) => ".",
+                };
+                ContigError::MixedElements {
+                    path: format!("{}{}{}", segment, separator, path),
+                    index,
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+impl core::fmt::Display for ContigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ContigError::BufferTooShort { expected, actual } => write!(
+                f,
+                "buffer too short for layout: expected {} scalars, got {}",
+                expected, actual
+            ),
+            ContigError::BufferTooLong { expected, actual } => write!(
+                f,
+                "buffer too long for layout: expected {} scalars, got {}",
+                expected, actual
+            ),
+            ContigError::Overflow => f.write_str("layout footprint overflows usize"),
+            ContigError::InvalidConfig { reason } => write!(f, "invalid config: {}", reason),
+            ContigError::InvalidPath { path, reason } => {
+                write!(f, "invalid path `{}`: {}", path, reason)
+            }
+            ContigError::MixedElements { path, index } => {
+                write!(f, "array elements 0 and {} ", index)?;
+                if !path.is_empty() {
+                    write!(f, "of `{}` ", path)?;
+                }
+                f.write_str("need different configs; use `Ragged` for per-element configs")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ContigError {}
 
 // ---------- Slice range cursor (linear, disjoint) ----------
 
 /// A tiny "allocator" that carves disjoint ranges from a linear buffer.
+#[derive(Default)]
 pub struct TakeCursor {
     idx: usize,
 }
@@ -27,11 +186,14 @@ impl TakeCursor {
     }
     /// Reserve the next `n` slots in the buffer and return their range.
     pub fn take_range(&mut self, n: usize) -> Range<usize> {
+        self.try_take_range(n)
+            .expect("overflow in TakeCursor::take_range")
+    }
+    /// Reserve the next `n` slots, reporting [`ContigError::Overflow`] instead of panicking.
+    pub fn try_take_range(&mut self, n: usize) -> Result<Range<usize>, ContigError> {
         let start = self.idx;
-        self.idx = start
-            .checked_add(n)
-            .expect("overflow in TakeCursor::take_range");
-        start..self.idx
+        self.idx = start.checked_add(n).ok_or(ContigError::Overflow)?;
+        Ok(start..self.idx)
     }
     /// Finish carving ranges and report the total footprint that was consumed.
     pub fn finish(self) -> usize {
@@ -39,6 +201,30 @@ impl TakeCursor {
     }
 }
 
+// ---------- Layout handle (borrowed or owned) ----------
+
+/// Layout metadata held by a view: borrowed from a cached layout, or owned when the view
+/// was built from a compile-time [`ContigStatic::LAYOUT`] that has no storage to borrow.
+#[derive(Clone, Debug)]
+pub enum LayoutRef<'a, L> {
+    /// Layout borrowed from caller-owned metadata.
+    Borrowed(&'a L),
+    /// Layout owned by the view itself.
+    Owned(L),
+}
+
+impl<'a, L> core::ops::Deref for LayoutRef<'a, L> {
+    type Target = L;
+
+    #[inline]
+    fn deref(&self) -> &L {
+        match self {
+            LayoutRef::Borrowed(layout) => layout,
+            LayoutRef::Owned(layout) => layout,
+        }
+    }
+}
+
 // ---------- Contig trait ----------
 
 /// Describes how a value occupies and views a region inside a flat `[F]` slice.
@@ -71,13 +257,607 @@ pub trait Contig<F> {
     fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a>;
     /// Build a mutable view into `buf` using this layout.
     fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a>;
+
+    /// Compute layout metadata, reporting overflow or invalid configs instead of panicking.
+    ///
+    /// The default defers to [`Contig::layout`]; adapters whose footprint can overflow
+    /// override it.
+    fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+        Ok(Self::layout(config))
+    }
+    /// Build a read-only view, failing if `buf` is shorter than the layout.
+    fn try_view<'a>(
+        layout: &'a Self::Layout,
+        buf: &'a [F],
+    ) -> Result<Self::ConstView<'a>, ContigError> {
+        ContigError::check_len(Self::len(layout), buf.len())?;
+        Ok(Self::view(layout, buf))
+    }
+    /// Build a mutable view, failing if `buf` is shorter than the layout.
+    fn try_view_mut<'a>(
+        layout: &'a Self::Layout,
+        buf: &'a mut [F],
+    ) -> Result<Self::MutView<'a>, ContigError> {
+        ContigError::check_len(Self::len(layout), buf.len())?;
+        Ok(Self::view_mut(layout, buf))
+    }
+
+    /// Reflect this layout as a [`SchemaNode`] tree placed at scalar `offset`.
+    ///
+    /// The default reports an [`SchemaKind::Opaque`] leaf spanning the whole footprint;
+    /// adapters with meaningful structure override it.
+    fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+        SchemaNode::new(SchemaKind::Opaque, offset..offset + Self::len(layout))
+    }
+    /// Path of the field holding scalar `index` (see [`SchemaNode::path_of`]).
+    fn path_of(layout: &Self::Layout, index: usize) -> Option<String> {
+        Self::schema(layout, 0).path_of(index)
+    }
+    /// Copy the data two layouts of this type have in common from `src` (laid out by
+    /// `src_layout`) into `dst` (laid out by `dst_layout`), leaving other slots untouched.
+    ///
+    /// The default copies everything when both footprints match and nothing otherwise;
+    /// resizable adapters override it to keep their overlapping elements.
+    fn copy_overlap(src_layout: &Self::Layout, src: &[F], dst_layout: &Self::Layout, dst: &mut [F])
+    where
+        F: Clone,
+    {
+        let len = Self::len(src_layout);
+        if len == Self::len(dst_layout) {
+            dst[..len].clone_from_slice(&src[..len]);
+        }
+    }
+    /// One name per scalar slot in buffer order (see [`SchemaNode::flat_names`]).
+    fn flat_names(layout: &Self::Layout) -> Vec<String> {
+        Self::schema(layout, 0).flat_names()
+    }
+    /// Absolute scalar range named by `path` (see [`SchemaNode::range_of`]).
+    fn range_of(layout: &Self::Layout, path: &str) -> Result<Range<usize>, ContigError> {
+        Self::schema(layout, 0).range_of(path)
+    }
+    /// Build-independent fingerprint of the layout's shape and the scalar type `F` (see
+    /// [`SchemaNode::scalar_schema_hash`]).
+    fn schema_hash(layout: &Self::Layout) -> u64 {
+        Self::schema(layout, 0).scalar_schema_hash::<F>()
+    }
+}
+
+// ---------- Static footprint ----------
+
+/// Companion to [`Contig`] for types whose footprint is known at compile time.
+///
+/// Static types carry their layout as an associated constant, so callers can size a stack
+/// buffer as `[F; T::LEN]` and build views straight from it without a runtime config or a
+/// cached `Layout`.  `#[contig]` implements this trait automatically whenever every field of
+/// a struct is itself static.
+pub trait ContigStatic<F>: Contig<F> {
+    /// Scalar footprint shared by every value of this type.
+    const LEN: usize;
+    /// Layout metadata, identical to what [`Contig::layout`] computes at runtime.
+    const LAYOUT: Self::Layout;
+
+    /// Build a read-only view into `buf` using [`ContigStatic::LAYOUT`].
+    fn view_static<'a>(buf: &'a [F]) -> Self::ConstView<'a>
+    where
+        F: 'a,
+        Self::Layout: 'a;
+    /// Build a mutable view into `buf` using [`ContigStatic::LAYOUT`].
+    fn view_static_mut<'a>(buf: &'a mut [F]) -> Self::MutView<'a>
+    where
+        F: 'a,
+        Self::Layout: 'a;
+}
+
+// ---------- Owned values ----------
+
+/// Companion to [`Contig`] for types with an ordinary owned counterpart, so code that does
+/// not care about the flat buffer can work with plain Rust values.
+///
+/// `#[contig(scalar = .., owned)]` generates a `Value` struct and implements this trait;
+/// elements of empty arrays are configured with [`empty_config`](Self::empty_config).
+pub trait ContigValue<F>: Contig<F> {
+    /// Owned value mirroring the layout, e.g. `Vec<T::Value>` for `Dyn<[T]>`.
+    type Value;
+
+    /// Config whose layout fits `value`, with runtime lengths taken from the value.
+    ///
+    /// Panics if the elements of a `Dyn` or fixed-size array need different configs, which
+    /// only `Ragged` can describe; [`try_config_of`](Self::try_config_of) reports that as
+    /// an error naming the array instead.
+    fn config_of(value: &Self::Value) -> Self::Config;
+    /// Like [`config_of`](Self::config_of), reporting [`ContigError::MixedElements`]
+    /// instead of panicking.
+    ///
+    /// The default defers to `config_of`; arrays and types containing them override it.
+    fn try_config_of(value: &Self::Value) -> Result<Self::Config, ContigError> {
+        Ok(Self::config_of(value))
+    }
+    /// Config for the elements of an empty array, which have no value to take one from:
+    /// zero lengths, absent `Opt`s, and the first variant of an enum.
+    fn empty_config() -> Self::Config;
+    /// Write `value` into `buf`, laid out by `layout`.
+    ///
+    /// Panics if the value does not fit the layout (e.g. a `Dyn` length mismatch).
+    fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]);
+    /// Read an owned value back out of `buf`, laid out by `layout`.
+    fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value;
+}
+
+// ---------- Layout schema (reflection) ----------
+
+/// One step from a parent [`SchemaNode`] to a child.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A named field of a struct or enum variant.
+    Field(&'static str),
+    /// An element of an array-like adapter.
+    Index(usize),
+}
+
+impl core::fmt::Display for Segment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Segment::Field(name) => f.write_str(name),
+            Segment::Index(i) => write!(f, "[{}]", i),
+        }
+    }
+}
+
+/// What a [`SchemaNode`] describes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// A single scalar slot.
+    Scalar,
+    /// A struct whose children are its fields.
+    Struct,
+    /// An enum laid out as the selected variant; its children are that variant's fields.
+    Enum {
+        /// Name of the selected variant.
+        variant: &'static str,
+    },
+    /// An array-like adapter (`[T; N]`, `Dyn<[T]>`, `Ragged<[T]>`) with indexed children.
+    Array,
+    /// A column-major `rows x cols` block of scalars.
+    Matrix {
+        /// Number of rows.
+        rows: usize,
+        /// Number of columns.
+        cols: usize,
+    },
+    /// An `Opt<T>` whose value is absent and spans nothing.
+    Absent,
+    /// A value whose implementation does not describe its internals.
+    Opaque,
+}
+
+impl core::fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SchemaKind::Scalar => f.write_str("scalar"),
+            SchemaKind::Struct => f.write_str("struct"),
+            SchemaKind::Enum { variant } => write!(f, "enum {}", variant),
+            SchemaKind::Array => f.write_str("array"),
+            SchemaKind::Matrix { rows, cols } => write!(f, "matrix {}x{}", rows, cols),
+            SchemaKind::Absent => f.write_str("absent"),
+            SchemaKind::Opaque => f.write_str("opaque"),
+        }
+    }
+}
+
+/// Node in the reflected tree of a layout: which scalars a value spans and how it splits
+/// into named or indexed children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaNode {
+    /// Step from the parent to this node (`None` at the root).
+    pub segment: Option<Segment>,
+    /// What this node describes.
+    pub kind: SchemaKind,
+    /// Absolute scalar range covered by this node.
+    pub range: Range<usize>,
+    /// Child nodes in layout order.
+    pub children: Vec<SchemaNode>,
+    /// Adapters that produced this node, outermost first (`["Opt", "Dyn"]` for a present
+    /// `Opt<Dyn<[T]>>`); empty for scalars and derived types. Tells apart adapters whose
+    /// nodes otherwise look alike, such as `Dyn<[T]>` and `[T; N]`.
+    pub adapters: Vec<&'static str>,
+}
+
+impl SchemaNode {
+    /// Create a childless root node.
+    pub fn new(kind: SchemaKind, range: Range<usize>) -> Self {
+        Self {
+            segment: None,
+            kind,
+            range,
+            children: Vec::new(),
+            adapters: Vec::new(),
+        }
+    }
+    /// Attach the step from the parent to this node.
+    pub fn with_segment(mut self, segment: Segment) -> Self {
+        self.segment = Some(segment);
+        self
+    }
+    /// Replace the children of this node.
+    pub fn with_children(mut self, children: Vec<SchemaNode>) -> Self {
+        self.children = children;
+        self
+    }
+    /// Record that `adapter` wraps everything already recorded for this node.
+    pub fn with_adapter(mut self, adapter: &'static str) -> Self {
+        self.adapters.insert(0, adapter);
+        self
+    }
+    /// Whether this node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+    /// Human-readable path from this node to scalar `index`, e.g. `links[3].pos.y`.
+    ///
+    /// Enums name their active variant before its fields, as in `joints[0].Revolute.angle`.
+    /// Matrix entries end in `[(row, col)]`; scalars inside a multi-scalar leaf without
+    /// further structure end in `[k]`. Returns `None` if `index` lies outside this node.
+    pub fn path_of(&self, index: usize) -> Option<String> {
+        use core::fmt::Write;
+
+        if !self.range.contains(&index) {
+            return None;
+        }
+        let mut path = String::new();
+        let mut node = self;
+        let push_field = |path: &mut String, name: &str| {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(name);
+        };
+        while let Some(child) = node.children.iter().find(|c| c.range.contains(&index)) {
+            if let SchemaKind::Enum { variant } = node.kind {
+                push_field(&mut path, variant);
+            }
+            match &child.segment {
+                Some(Segment::Field(name)) => push_field(&mut path, name),
+                Some(Segment::Index(i)) => write!(path, "[{}]", i).unwrap(),
+                None => {}
+            }
+            node = child;
+        }
+        let local = index - node.range.start;
+        match node.kind {
+            SchemaKind::Matrix { rows, .. } => {
+                write!(path, "[({}, {})]", local % rows, local / rows).unwrap()
+            }
+            _ if node.range.len() > 1 => write!(path, "[{}]", local).unwrap(),
+            _ => {}
+        }
+        Some(path)
+    }
+    /// Resolve a path such as `links[2].pos.x` or `mats[0][(1, 2)]` to its absolute scalar
+    /// range; this is the inverse of [`path_of`](Self::path_of). An empty path names this node.
+    pub fn range_of(&self, path: &str) -> Result<Range<usize>, ContigError> {
+        let invalid = |reason| ContigError::InvalidPath {
+            path: path.to_owned(),
+            reason,
+        };
+        let steps = parse_path(path).map_err(invalid)?;
+        let mut node = self;
+        // An enum's fields are only reachable through the name of its active variant.
+        let mut variant = None;
+        for (n, step) in steps.iter().enumerate() {
+            match (&node.kind, step) {
+                (&SchemaKind::Enum { variant: active }, _) if variant == Some(active) => {}
+                (&SchemaKind::Enum { variant: active }, PathStep::Field(name))
+                    if *name == active =>
+                {
+                    variant = Some(active);
+                    continue;
+                }
+                (SchemaKind::Enum { .. }, _) => return Err(invalid("not the active enum variant")),
+                _ => {}
+            }
+            let child = node
+                .children
+                .iter()
+                .find(|child| match (&child.segment, step) {
+                    (Some(Segment::Field(name)), PathStep::Field(field)) => name == field,
+                    (Some(Segment::Index(i)), PathStep::Index(index)) => i == index,
+                    _ => false,
+                });
+            if let Some(child) = child {
+                node = child;
+                variant = None;
+                continue;
+            }
+            // Past the described structure only single scalars can be addressed.
+            let local = match (*step, &node.kind) {
+                (PathStep::Entry(row, col), &SchemaKind::Matrix { rows, cols }) => {
+                    if row >= rows || col >= cols {
+                        return Err(invalid("matrix entry out of bounds"));
+                    }
+                    col * rows + row
+                }
+                (PathStep::Entry(..), _) => return Err(invalid("entry of a non-matrix")),
+                (PathStep::Index(i), _) if node.is_leaf() && i < node.range.len() => i,
+                (PathStep::Index(_), _) => return Err(invalid("index out of bounds")),
+                (PathStep::Field(_), _) => return Err(invalid("no such field")),
+            };
+            if n + 1 != steps.len() {
+                return Err(invalid("path continues past a scalar"));
+            }
+            let start = node.range.start + local;
+            return Ok(start..start + 1);
+        }
+        Ok(node.range.clone())
+    }
+    /// Borrow the scalars named by `path` from `buf` (see [`range_of`](Self::range_of)).
+    pub fn get_path<'b, F>(&self, buf: &'b [F], path: &str) -> Result<&'b [F], ContigError> {
+        let range = self.range_of(path)?;
+        ContigError::check_len(range.end, buf.len())?;
+        Ok(&buf[range])
+    }
+    /// Mutably borrow the scalars named by `path` from `buf`.
+    pub fn get_path_mut<'b, F>(
+        &self,
+        buf: &'b mut [F],
+        path: &str,
+    ) -> Result<&'b mut [F], ContigError> {
+        let range = self.range_of(path)?;
+        ContigError::check_len(range.end, buf.len())?;
+        Ok(&mut buf[range])
+    }
+    /// One name per scalar of this node in buffer order, such as `links.0.pos.x`, for CSV
+    /// headers, plot legends, or solver variable names.
+    ///
+    /// Segments are joined with `.` and enums contribute their active variant, as in
+    /// `joints.0.Revolute.angle`; matrix entries are named `(row,col)` and scalars inside
+    /// a multi-scalar leaf without further structure by their local index.
+    pub fn flat_names(&self) -> Vec<String> {
+        let mut names = vec![String::new(); self.range.len()];
+        self.fill_flat_names(String::new(), self.range.start, &mut names);
+        names
+    }
+
+    fn fill_flat_names(&self, prefix: String, base: usize, names: &mut [String]) {
+        let prefix = match self.kind {
+            SchemaKind::Enum { variant } if prefix.is_empty() => variant.to_owned(),
+            SchemaKind::Enum { variant } => format!("{}.{}", prefix, variant),
+            _ => prefix,
+        };
+        let join = |suffix: core::fmt::Arguments<'_>| {
+            if prefix.is_empty() {
+                suffix.to_string()
+            } else {
+                format!("{}.{}", prefix, suffix)
+            }
+        };
+        if !self.is_leaf() {
+            for child in &self.children {
+                let prefix = match &child.segment {
+                    Some(segment @ Segment::Field(_)) => join(format_args!("{}", segment)),
+                    Some(Segment::Index(i)) => join(format_args!("{}", i)),
+                    None => prefix.clone(),
+                };
+                child.fill_flat_names(prefix, base, names);
+            }
+            return;
+        }
+        for (local, index) in self.range.clone().enumerate() {
+            names[index - base] = match self.kind {
+                SchemaKind::Matrix { rows, .. } => {
+                    join(format_args!("({},{})", local % rows, local / rows))
+                }
+                _ if self.range.len() > 1 => join(format_args!("{}", local)),
+                _ => prefix.clone(),
+            };
+        }
+    }
+    /// Stable 64-bit fingerprint (FNV-1a) of this tree's shape: field names and order,
+    /// indices, node kinds with variant names and matrix sizes, adapters, and ranges relative
+    /// to this node's start.
+    ///
+    /// Only the structure feeds the hash, never Rust type ids, so equal shapes hash equally
+    /// across builds, platforms, and processes. The scalar type is not included; see
+    /// [`scalar_schema_hash`](Self::scalar_schema_hash) for buffers that cross processes.
+    pub fn schema_hash(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        self.hash_shape(self.range.start, &mut hash);
+        hash.finish()
+    }
+    /// [`schema_hash`](Self::schema_hash) that also covers the scalar type `F`, by its
+    /// [`type_name`](core::any::type_name) (`f32`, `f64`), so an `f32` producer and an `f64`
+    /// consumer of the same layout never agree. This is what [`Contig::schema_hash`] returns.
+    pub fn scalar_schema_hash<F: ?Sized>(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash.write_str(core::any::type_name::<F>());
+        self.hash_shape(self.range.start, &mut hash);
+        hash.finish()
+    }
+
+    fn hash_shape(&self, base: usize, hash: &mut Fnv1a) {
+        match &self.segment {
+            None => hash.write_u8(0),
+            Some(Segment::Field(name)) => {
+                hash.write_u8(1);
+                hash.write_str(name);
+            }
+            Some(Segment::Index(i)) => {
+                hash.write_u8(2);
+                hash.write_u64(*i as u64);
+            }
+        }
+        match &self.kind {
+            SchemaKind::Scalar => hash.write_u8(0),
+            SchemaKind::Struct => hash.write_u8(1),
+            SchemaKind::Enum { variant } => {
+                hash.write_u8(2);
+                hash.write_str(variant);
+            }
+            SchemaKind::Array => hash.write_u8(3),
+            SchemaKind::Matrix { rows, cols } => {
+                hash.write_u8(4);
+                hash.write_u64(*rows as u64);
+                hash.write_u64(*cols as u64);
+            }
+            SchemaKind::Absent => hash.write_u8(5),
+            SchemaKind::Opaque => hash.write_u8(6),
+        }
+        hash.write_u64(self.adapters.len() as u64);
+        for adapter in &self.adapters {
+            hash.write_str(adapter);
+        }
+        hash.write_u64((self.range.start - base) as u64);
+        hash.write_u64((self.range.end - base) as u64);
+        hash.write_u64(self.children.len() as u64);
+        for child in &self.children {
+            child.hash_shape(base, hash);
+        }
+    }
+
+    /// Iterate over this node and all of its descendants in depth-first pre-order.
+    pub fn iter(&self) -> impl Iterator<Item = &SchemaNode> {
+        let mut stack = vec![self];
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    fn fmt_indented(&self, f: &mut core::fmt::Formatter<'_>, depth: usize) -> core::fmt::Result {
+        match &self.segment {
+            Some(segment) => write!(f, "{:indent$}{}: ", "", segment, indent = depth * 2)?,
+            None => write!(f, "{:indent$}", "", indent = depth * 2)?,
+        }
+        write!(f, "{}", self.kind)?;
+        if !self.adapters.is_empty() {
+            write!(f, " ({})", self.adapters.join(" "))?;
+        }
+        writeln!(f, " {}..{}", self.range.start, self.range.end)?;
+        self.children
+            .iter()
+            .try_for_each(|child| child.fmt_indented(f, depth + 1))
+    }
+}
+
+/// Prints one indented line per node, e.g. `pos: struct 1..4` or `links: array (Dyn) 4..12`.
+impl core::fmt::Display for SchemaNode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// 64-bit FNV-1a over a byte stream; integers are fed little-endian and strings
+/// length-prefixed so the encoding is unambiguous and platform independent.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// One parsed step of a textual field path.
+#[derive(Clone, Copy)]
+enum PathStep<'p> {
+    Field(&'p str),
+    Index(usize),
+    Entry(usize, usize),
+}
+
+/// Split `links[2].pos.x` / `mats[0][(1, 2)]` into steps.
+fn parse_path(path: &str) -> Result<Vec<PathStep<'_>>, &'static str> {
+    fn number(text: &str) -> Result<usize, &'static str> {
+        text.trim().parse().map_err(|_| "expected an index")
+    }
+
+    let mut steps = Vec::new();
+    let mut rest = path.trim();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (inner, after) = after.split_once(']').ok_or("unclosed `[`")?;
+            let inner = inner.trim();
+            match inner
+                .strip_prefix('(')
+                .and_then(|pair| pair.strip_suffix(')'))
+            {
+                Some(pair) => {
+                    let (row, col) = pair.split_once(',').ok_or("expected `[(row, col)]`")?;
+                    steps.push(PathStep::Entry(number(row)?, number(col)?));
+                }
+                None => steps.push(PathStep::Index(number(inner)?)),
+            }
+            rest = after;
+        } else {
+            let body = match rest.strip_prefix('.') {
+                Some(body) => body,
+                None if steps.is_empty() => rest,
+                None => return Err("expected `.` or `[`"),
+            };
+            let end = body.find(['.', '[']).unwrap_or(body.len());
+            let name = body[..end].trim();
+            if name.is_empty() {
+                return Err("empty field name");
+            }
+            steps.push(PathStep::Field(name));
+            rest = &body[end..];
+        }
+    }
+    Ok(steps)
+}
+
+/// Copy the overlap of the first `count` equally sized elements of two arrays.
+fn copy_array_overlap<F: Clone, T: Contig<F>>(
+    (src_elem, src_len, src): (&T::Layout, usize, &[F]),
+    (dst_elem, dst_len, dst): (&T::Layout, usize, &mut [F]),
+    count: usize,
+) {
+    for i in 0..count {
+        T::copy_overlap(
+            src_elem,
+            &src[i * src_len..(i + 1) * src_len],
+            dst_elem,
+            &mut dst[i * dst_len..(i + 1) * dst_len],
+        );
+    }
+}
+
+/// Schema nodes for `count` equally sized elements starting at `offset`.
+fn array_schema<F, T: Contig<F>>(
+    elem_layout: &T::Layout,
+    elem_len: usize,
+    count: usize,
+    offset: usize,
+) -> SchemaNode {
+    let children = (0..count)
+        .map(|i| T::schema(elem_layout, offset + i * elem_len).with_segment(Segment::Index(i)))
+        .collect();
+    SchemaNode::new(SchemaKind::Array, offset..offset + count * elem_len).with_children(children)
 }
 
 // ---------- Scalars ----------
 
 /// Trivial layout marker for scalar types where the layout metadata carries no
 /// additional information.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScalarLayout;
 
 macro_rules! impl_contig_scalar {
@@ -97,6 +877,10 @@ macro_rules! impl_contig_scalar {
                     1
                 }
 
+                fn schema(_layout: &Self::Layout, offset: usize) -> SchemaNode {
+                    SchemaNode::new(SchemaKind::Scalar, offset..offset + 1)
+                }
+
                 fn view<'a>(_layout: &'a Self::Layout, buf: &'a [$t]) -> Self::ConstView<'a> {
                     debug_assert!(buf.len() >= 1);
                     &buf[0]
@@ -107,19 +891,140 @@ macro_rules! impl_contig_scalar {
                     &mut buf[0]
                 }
             }
+
+            impl ContigValue<$t> for $t {
+                type Value = $t;
+
+                fn config_of(_value: &Self::Value) -> Self::Config {}
+
+                fn empty_config() -> Self::Config {}
+
+                fn pack(value: &Self::Value, _layout: &Self::Layout, buf: &mut [$t]) {
+                    buf[0] = *value;
+                }
+
+                fn unpack(_layout: &Self::Layout, buf: &[$t]) -> Self::Value {
+                    buf[0]
+                }
+            }
+
+            impl ContigStatic<$t> for $t {
+                const LEN: usize = 1;
+                const LAYOUT: Self::Layout = ScalarLayout;
+
+                fn view_static<'a>(buf: &'a [$t]) -> Self::ConstView<'a>
+                where
+                    $t: 'a,
+                {
+                    Self::view(&ScalarLayout, buf)
+                }
+
+                fn view_static_mut<'a>(buf: &'a mut [$t]) -> Self::MutView<'a>
+                where
+                    $t: 'a,
+                {
+                    Self::view_mut(&ScalarLayout, buf)
+                }
+            }
         )*
     };
 }
 
 impl_contig_scalar!(f32, f64);
 
+// ---------- Vector-space operations ----------
+
+/// Floating-point scalars that support the whole-view arithmetic in [`ops`] (`fill`,
+/// `copy_from`, `axpy`, `scale`, `dot`, `norm`).
+pub trait Real:
+    Copy
+    + PartialOrd
+    + core::ops::Add<Output = Self>
+    + core::ops::Mul<Output = Self>
+    + core::ops::AddAssign
+    + core::ops::MulAssign
+{
+    /// Additive identity.
+    const ZERO: Self;
+
+    /// Square root, used by [`ops::norm`].
+    fn sqrt(self) -> Self;
+}
+
+macro_rules! impl_real {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Real for $t {
+                const ZERO: Self = 0.0;
+
+                #[inline]
+                fn sqrt(self) -> Self {
+                    <$t>::sqrt(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_real!(f32, f64);
+
+/// Slice kernels behind the arithmetic methods on views; each operates on exactly the
+/// scalars it is given, so a view passes only the sub-range it covers.
+pub mod ops {
+    use super::Real;
+
+    /// Set every scalar to `value`.
+    #[inline]
+    pub fn fill<F: Real>(dst: &mut [F], value: F) {
+        dst.fill(value);
+    }
+
+    /// Overwrite `dst` with `src` (panics if the lengths differ).
+    #[inline]
+    pub fn copy_from<F: Real>(dst: &mut [F], src: &[F]) {
+        assert_eq!(dst.len(), src.len(), "views span different lengths");
+        dst.copy_from_slice(src);
+    }
+
+    /// `y += alpha * x` (panics if the lengths differ).
+    #[inline]
+    pub fn axpy<F: Real>(alpha: F, x: &[F], y: &mut [F]) {
+        assert_eq!(x.len(), y.len(), "views span different lengths");
+        for (y, &x) in y.iter_mut().zip(x) {
+            *y += alpha * x;
+        }
+    }
+
+    /// `x *= alpha`.
+    #[inline]
+    pub fn scale<F: Real>(alpha: F, x: &mut [F]) {
+        for x in x {
+            *x *= alpha;
+        }
+    }
+
+    /// Sum of elementwise products (panics if the lengths differ).
+    #[inline]
+    pub fn dot<F: Real>(x: &[F], y: &[F]) -> F {
+        assert_eq!(x.len(), y.len(), "views span different lengths");
+        x.iter().zip(y).fold(F::ZERO, |acc, (&x, &y)| acc + x * y)
+    }
+
+    /// Euclidean norm.
+    #[inline]
+    pub fn norm<F: Real>(x: &[F]) -> F {
+        dot(x, x).sqrt()
+    }
+}
+
 // ---------- Dyn<[T]> (dynamic arrays) ----------
 
 /// Marker type representing a runtime-sized slice of contiguous `T` values.
 pub struct Dyn<T: ?Sized>(PhantomData<T>);
 
 /// Configuration for a runtime-sized contiguous array of `T`.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DynArrayConfig<TCfg> {
     /// Number of `T` elements to expose through the view.
     pub len: usize,
@@ -128,7 +1033,8 @@ pub struct DynArrayConfig<TCfg> {
 }
 
 /// Fully computed layout information for a runtime-sized array of `T`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DynArrayLayout<TLayout> {
     /// Number of elements contained in this layout.
     pub len: usize,
@@ -173,6 +1079,11 @@ where
         self.count
     }
     #[inline]
+    /// Whether this view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    #[inline]
     /// Fetch a read-only view for element `i` (panics in debug if out of bounds).
     pub fn get(&self, i: usize) -> T::ConstView<'_> {
         debug_assert!(i < self.count);
@@ -180,6 +1091,45 @@ where
         let end = start + self.elem_len;
         T::view(&self.elem_layout, &self.base[start..end])
     }
+    #[inline]
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> ElemIter<'_, F, T> {
+        ElemIter::new(
+            &self.base[..self.count * self.elem_len],
+            self.count,
+            &self.elem_layout,
+            self.elem_len,
+        )
+    }
+    /// Read-only subview over the elements in `range` (panics if out of bounds).
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DynArrayConstView<'_, F, T> {
+        let range = elem_range(range, self.count);
+        DynArrayConstView {
+            base: &self.base[range.start * self.elem_len..range.end * self.elem_len],
+            count: range.len(),
+            elem_layout: self.elem_layout.clone(),
+            elem_len: self.elem_len,
+        }
+    }
+    /// Split into read-only subviews over `[0, mid)` and `[mid, len)` (panics if
+    /// `mid > len`).
+    pub fn split_at(
+        &self,
+        mid: usize,
+    ) -> (DynArrayConstView<'_, F, T>, DynArrayConstView<'_, F, T>) {
+        (self.slice(..mid), self.slice(mid..))
+    }
+    /// Iterate over read-only subviews of `size` elements; the last one may be shorter.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = DynArrayConstView<'_, F, T>> {
+        assert!(size > 0, "chunk size must be non-zero");
+        (0..self.count)
+            .step_by(size)
+            .map(move |start| self.slice(start..(start + size).min(self.count)))
+    }
+    /// Iterate over neighbouring element pairs `(k, k + 1)`, like `windows(2)`.
+    pub fn pairs(&self) -> impl Iterator<Item = (T::ConstView<'_>, T::ConstView<'_>)> {
+        (1..self.count).map(move |i| (self.get(i - 1), self.get(i)))
+    }
 }
 
 impl<'a, F, T> DynArrayMutView<'a, F, T>
@@ -193,6 +1143,11 @@ where
         self.count
     }
     #[inline]
+    /// Whether this view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    #[inline]
     /// Fetch a mutable view for element `i` (panics in debug if out of bounds).
     pub fn get_mut(&mut self, i: usize) -> T::MutView<'_> {
         debug_assert!(i < self.count);
@@ -208,12 +1163,224 @@ where
         let end = start + self.elem_len;
         T::view(&self.elem_layout, &self.base[start..end])
     }
+    /// Fetch mutable views for several distinct elements at once, or `None` if any index
+    /// is out of bounds or repeated.
+    pub fn get_many_mut<const K: usize>(
+        &mut self,
+        indices: [usize; K],
+    ) -> Option<[T::MutView<'_>; K]> {
+        for (n, &i) in indices.iter().enumerate() {
+            if i >= self.count || indices[..n].contains(&i) {
+                return None;
+            }
+        }
+        let elem_len = self.elem_len;
+        let ranges = indices.map(|i| i * elem_len..(i + 1) * elem_len);
+        let bufs = self.base.get_disjoint_mut(ranges).ok()?;
+        let elem_layout = &self.elem_layout;
+        Some(bufs.map(|buf| T::view_mut(elem_layout, buf)))
+    }
+    /// Reborrow as a read-only view over the same elements.
+    pub fn as_const(&self) -> DynArrayConstView<'_, F, T> {
+        DynArrayConstView {
+            base: &self.base[..self.count * self.elem_len],
+            count: self.count,
+            elem_layout: self.elem_layout.clone(),
+            elem_len: self.elem_len,
+        }
+    }
+    #[inline]
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> ElemIter<'_, F, T> {
+        ElemIter::new(
+            &self.base[..self.count * self.elem_len],
+            self.count,
+            &self.elem_layout,
+            self.elem_len,
+        )
+    }
+    #[inline]
+    /// Iterate over mutable views of every element in order.
+    pub fn iter_mut(&mut self) -> ElemIterMut<'_, F, T> {
+        ElemIterMut::new(
+            &mut self.base[..self.count * self.elem_len],
+            self.count,
+            &self.elem_layout,
+            self.elem_len,
+        )
+    }
+    /// Read-only subview over the elements in `range` (panics if out of bounds).
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DynArrayConstView<'_, F, T> {
+        let range = elem_range(range, self.count);
+        DynArrayConstView {
+            base: &self.base[range.start * self.elem_len..range.end * self.elem_len],
+            count: range.len(),
+            elem_layout: self.elem_layout.clone(),
+            elem_len: self.elem_len,
+        }
+    }
+    /// Mutable subview over the elements in `range` (panics if out of bounds).
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> DynArrayMutView<'_, F, T> {
+        let range = elem_range(range, self.count);
+        DynArrayMutView {
+            base: &mut self.base[range.start * self.elem_len..range.end * self.elem_len],
+            count: range.len(),
+            elem_layout: self.elem_layout.clone(),
+            elem_len: self.elem_len,
+        }
+    }
+    /// Split into disjoint mutable subviews over `[0, mid)` and `[mid, len)` (panics if
+    /// `mid > len`).
+    pub fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (DynArrayMutView<'_, F, T>, DynArrayMutView<'_, F, T>) {
+        assert!(mid <= self.count, "split index out of bounds");
+        let (head, tail) =
+            self.base[..self.count * self.elem_len].split_at_mut(mid * self.elem_len);
+        (
+            DynArrayMutView {
+                base: head,
+                count: mid,
+                elem_layout: self.elem_layout.clone(),
+                elem_len: self.elem_len,
+            },
+            DynArrayMutView {
+                base: tail,
+                count: self.count - mid,
+                elem_layout: self.elem_layout.clone(),
+                elem_len: self.elem_len,
+            },
+        )
+    }
+    /// Iterate over disjoint mutable subviews of `size` elements; the last one may be
+    /// shorter.
+    pub fn chunks_mut(&mut self, size: usize) -> impl Iterator<Item = DynArrayMutView<'_, F, T>> {
+        assert!(size > 0, "chunk size must be non-zero");
+        let (count, elem_len, elem_layout) = (self.count, self.elem_len, &self.elem_layout);
+        let mut rest = &mut self.base[..count * elem_len];
+        (0..count).step_by(size).map(move |start| {
+            let n = size.min(count - start);
+            let (head, tail) = core::mem::take(&mut rest).split_at_mut(n * elem_len);
+            rest = tail;
+            DynArrayMutView {
+                base: head,
+                count: n,
+                elem_layout: elem_layout.clone(),
+                elem_len,
+            }
+        })
+    }
+    /// Iterate over neighbouring element pairs `(k, k + 1)`, like `windows(2)`; use
+    /// [`get_many_mut`](Self::get_many_mut) to update a pair in place.
+    pub fn pairs(&self) -> impl Iterator<Item = (T::ConstView<'_>, T::ConstView<'_>)> {
+        (1..self.count).map(move |i| (self.get(i - 1), self.get(i)))
+    }
 }
 
-// Dynamic array adapter backed by consecutive `T` layouts.
-impl<F, T> Contig<F> for Dyn<[T]>
+// Array views print their elements as a list, e.g. `[Link { mass: 1.0, .. }, ..]`.
+impl<'a, F, T> core::fmt::Debug for DynArrayConstView<'a, F, T>
 where
-    T: Contig<F> + 'static,
+    T: Contig<F>,
+    T::Layout: Clone,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, F, T> core::fmt::Debug for DynArrayMutView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, F, T> DynArrayConstView<'a, F, T>
+where
+    F: Real,
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    /// Scalars spanned by the elements of this view.
+    fn scalars(&self) -> &[F] {
+        &self.base[..self.count * self.elem_len]
+    }
+    /// Dot product with another array of the same footprint.
+    pub fn dot(&self, other: &DynArrayConstView<'_, F, T>) -> F {
+        ops::dot(self.scalars(), other.scalars())
+    }
+    /// Euclidean norm over every scalar of every element.
+    pub fn norm(&self) -> F {
+        ops::norm(self.scalars())
+    }
+}
+
+impl<'a, F, T> DynArrayMutView<'a, F, T>
+where
+    F: Real,
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    /// Scalars spanned by the elements of this view.
+    fn scalars(&self) -> &[F] {
+        &self.base[..self.count * self.elem_len]
+    }
+    /// Scalars spanned by the elements of this view, mutably.
+    fn scalars_mut(&mut self) -> &mut [F] {
+        &mut self.base[..self.count * self.elem_len]
+    }
+    /// Set every scalar of every element to `value`.
+    pub fn fill(&mut self, value: F) {
+        ops::fill(self.scalars_mut(), value);
+    }
+    /// Overwrite the elements with those of `src` (panics if the footprints differ).
+    pub fn copy_from(&mut self, src: &DynArrayConstView<'_, F, T>) {
+        ops::copy_from(self.scalars_mut(), src.scalars());
+    }
+    /// `self += alpha * x`, elementwise (panics if the footprints differ).
+    pub fn axpy(&mut self, alpha: F, x: &DynArrayConstView<'_, F, T>) {
+        ops::axpy(alpha, x.scalars(), self.scalars_mut());
+    }
+    /// `self *= alpha`, elementwise.
+    pub fn scale(&mut self, alpha: F) {
+        ops::scale(alpha, self.scalars_mut());
+    }
+    /// Dot product with another array of the same footprint.
+    pub fn dot(&self, other: &DynArrayConstView<'_, F, T>) -> F {
+        ops::dot(self.scalars(), other.scalars())
+    }
+    /// Euclidean norm over every scalar of every element.
+    pub fn norm(&self) -> F {
+        ops::norm(self.scalars())
+    }
+}
+
+/// Resolve an element `range` against `count` elements (panics if it falls outside).
+fn elem_range(range: impl RangeBounds<usize>, count: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => count,
+    };
+    assert!(start <= end && end <= count, "element range out of bounds");
+    start..end
+}
+
+// Dynamic array adapter backed by consecutive `T` layouts.
+impl<F, T> Contig<F> for Dyn<[T]>
+where
+    T: Contig<F> + 'static,
     T::Layout: Clone + 'static,
 {
     type Config = DynArrayConfig<T::Config>;
@@ -226,40 +1393,2173 @@ where
     type MutView<'a>
         = DynArrayMutView<'a, F, T>
     where
-        F: 'a,
-        T::Layout: Clone;
+        F: 'a,
+        T::Layout: Clone;
+
+    fn layout(config: &Self::Config) -> Self::Layout {
+        let elem_layout = T::layout(&config.elem);
+        let elem_len = T::len(&elem_layout);
+        DynArrayLayout {
+            len: config.len,
+            elem_layout,
+            elem_len,
+        }
+    }
+
+    fn len(layout: &Self::Layout) -> usize {
+        layout.len * layout.elem_len
+    }
+
+    fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+        array_schema::<F, T>(&layout.elem_layout, layout.elem_len, layout.len, offset)
+            .with_adapter("Dyn")
+    }
+
+    fn copy_overlap(src_layout: &Self::Layout, src: &[F], dst_layout: &Self::Layout, dst: &mut [F])
+    where
+        F: Clone,
+    {
+        copy_array_overlap::<F, T>(
+            (&src_layout.elem_layout, src_layout.elem_len, src),
+            (&dst_layout.elem_layout, dst_layout.elem_len, dst),
+            src_layout.len.min(dst_layout.len),
+        );
+    }
+
+    fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+        let elem_layout = T::try_layout(&config.elem)?;
+        let elem_len = T::len(&elem_layout);
+        config
+            .len
+            .checked_mul(elem_len)
+            .ok_or(ContigError::Overflow)?;
+        Ok(DynArrayLayout {
+            len: config.len,
+            elem_layout,
+            elem_len,
+        })
+    }
+
+    fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        DynArrayConstView {
+            base: buf,
+            count: layout.len,
+            elem_layout: layout.elem_layout.clone(),
+            elem_len: layout.elem_len,
+        }
+    }
+
+    fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        DynArrayMutView {
+            base: buf,
+            count: layout.len,
+            elem_layout: layout.elem_layout.clone(),
+            elem_len: layout.elem_len,
+        }
+    }
+}
+
+impl<F, T> ContigValue<F> for Dyn<[T]>
+where
+    T: ContigValue<F> + 'static,
+    T::Config: PartialEq,
+    T::Layout: Clone + 'static,
+{
+    type Value = Vec<T::Value>;
+
+    fn config_of(value: &Self::Value) -> Self::Config {
+        Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_config_of(value: &Self::Value) -> Result<Self::Config, ContigError> {
+        Ok(DynArrayConfig {
+            len: value.len(),
+            elem: elem_config_of::<F, T>(value)?,
+        })
+    }
+
+    fn empty_config() -> Self::Config {
+        DynArrayConfig {
+            len: 0,
+            elem: T::empty_config(),
+        }
+    }
+
+    fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+        assert_eq!(
+            value.len(),
+            layout.len,
+            "value length does not match layout"
+        );
+        pack_elems::<F, T>(value, &layout.elem_layout, layout.elem_len, buf);
+    }
+
+    fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+        (0..layout.len)
+            .map(|i| {
+                let elem = &buf[i * layout.elem_len..(i + 1) * layout.elem_len];
+                T::unpack(&layout.elem_layout, elem)
+            })
+            .collect()
+    }
+}
+
+/// Shared element config of an array value; empty arrays fall back to
+/// [`ContigValue::empty_config`].
+fn elem_config_of<F, T>(values: &[T::Value]) -> Result<T::Config, ContigError>
+where
+    T: ContigValue<F>,
+    T::Config: PartialEq,
+{
+    let mut configs = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| T::try_config_of(value).map_err(|err| err.within(Segment::Index(i))));
+    let first = match configs.next() {
+        Some(first) => first?,
+        None => return Ok(T::empty_config()),
+    };
+    for (i, config) in configs.enumerate() {
+        if config? != first {
+            return Err(ContigError::MixedElements {
+                path: String::new(),
+                index: i + 1,
+            });
+        }
+    }
+    Ok(first)
+}
+
+/// Pack equally sized elements one after another.
+fn pack_elems<F, T: ContigValue<F>>(
+    values: &[T::Value],
+    elem_layout: &T::Layout,
+    elem_len: usize,
+    buf: &mut [F],
+) {
+    for (i, value) in values.iter().enumerate() {
+        T::pack(
+            value,
+            elem_layout,
+            &mut buf[i * elem_len..(i + 1) * elem_len],
+        );
+    }
+}
+
+// ---------- [T; N] (fixed-size arrays) ----------
+
+/// Fully computed layout information for a fixed-size array `[T; N]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayLayout<TLayout> {
+    /// Cached element layout metadata (reused when producing views).
+    pub elem_layout: TLayout,
+    /// Scalar footprint of a single element.
+    pub elem_len: usize,
+}
+
+/// Immutable view into a contiguous run of exactly `N` elements of type `T`.
+pub struct ArrayConstView<'a, F, T, const N: usize>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    base: &'a [F],
+    elem_layout: T::Layout,
+    elem_len: usize,
+}
+
+/// Mutable view into a contiguous run of exactly `N` elements of type `T`.
+pub struct ArrayMutView<'a, F, T, const N: usize>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    base: &'a mut [F],
+    elem_layout: T::Layout,
+    elem_len: usize,
+}
+
+impl<'a, F, T, const N: usize> ArrayConstView<'a, F, T, N>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    #[inline]
+    /// Number of elements contained in this view (always `N`).
+    pub fn len(&self) -> usize {
+        N
+    }
+    #[inline]
+    /// Whether this view contains no elements (only true for `N == 0`).
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+    #[inline]
+    /// Fetch a read-only view for element `i` (panics in debug if out of bounds).
+    pub fn get(&self, i: usize) -> T::ConstView<'_> {
+        debug_assert!(i < N);
+        let start = i * self.elem_len;
+        let end = start + self.elem_len;
+        T::view(&self.elem_layout, &self.base[start..end])
+    }
+    #[inline]
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> ElemIter<'_, F, T> {
+        ElemIter::new(
+            &self.base[..N * self.elem_len],
+            N,
+            &self.elem_layout,
+            self.elem_len,
+        )
+    }
+}
+
+impl<'a, F, T, const N: usize> ArrayMutView<'a, F, T, N>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+{
+    #[inline]
+    /// Number of elements contained in this view (always `N`).
+    pub fn len(&self) -> usize {
+        N
+    }
+    #[inline]
+    /// Whether this view contains no elements (only true for `N == 0`).
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+    #[inline]
+    /// Fetch a mutable view for element `i` (panics in debug if out of bounds).
+    pub fn get_mut(&mut self, i: usize) -> T::MutView<'_> {
+        debug_assert!(i < N);
+        let start = i * self.elem_len;
+        let end = start + self.elem_len;
+        T::view_mut(&self.elem_layout, &mut self.base[start..end])
+    }
+    #[inline]
+    /// Fetch a read-only view for element `i` (panics in debug if out of bounds).
+    pub fn get(&self, i: usize) -> T::ConstView<'_> {
+        debug_assert!(i < N);
+        let start = i * self.elem_len;
+        let end = start + self.elem_len;
+        T::view(&self.elem_layout, &self.base[start..end])
+    }
+    #[inline]
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> ElemIter<'_, F, T> {
+        ElemIter::new(
+            &self.base[..N * self.elem_len],
+            N,
+            &self.elem_layout,
+            self.elem_len,
+        )
+    }
+    #[inline]
+    /// Iterate over mutable views of every element in order.
+    pub fn iter_mut(&mut self) -> ElemIterMut<'_, F, T> {
+        let end = N * self.elem_len;
+        ElemIterMut::new(&mut self.base[..end], N, &self.elem_layout, self.elem_len)
+    }
+}
+
+impl<'a, F, T, const N: usize> core::fmt::Debug for ArrayConstView<'a, F, T, N>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, F, T, const N: usize> core::fmt::Debug for ArrayMutView<'a, F, T, N>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Fixed-size array adapter: `N` consecutive `T` layouts sharing one element config.
+impl<F, T, const N: usize> Contig<F> for [T; N]
+where
+    T: Contig<F> + 'static,
+    T::Layout: Clone + 'static,
+{
+    type Config = T::Config;
+    type Layout = ArrayLayout<T::Layout>;
+    type ConstView<'a>
+        = ArrayConstView<'a, F, T, N>
+    where
+        F: 'a,
+        T::Layout: Clone;
+    type MutView<'a>
+        = ArrayMutView<'a, F, T, N>
+    where
+        F: 'a,
+        T::Layout: Clone;
+
+    fn layout(config: &Self::Config) -> Self::Layout {
+        let elem_layout = T::layout(config);
+        let elem_len = T::len(&elem_layout);
+        ArrayLayout {
+            elem_layout,
+            elem_len,
+        }
+    }
+
+    fn len(layout: &Self::Layout) -> usize {
+        N * layout.elem_len
+    }
+
+    fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+        array_schema::<F, T>(&layout.elem_layout, layout.elem_len, N, offset).with_adapter("[T; N]")
+    }
+
+    fn copy_overlap(src_layout: &Self::Layout, src: &[F], dst_layout: &Self::Layout, dst: &mut [F])
+    where
+        F: Clone,
+    {
+        copy_array_overlap::<F, T>(
+            (&src_layout.elem_layout, src_layout.elem_len, src),
+            (&dst_layout.elem_layout, dst_layout.elem_len, dst),
+            N,
+        );
+    }
+
+    fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+        let elem_layout = T::try_layout(config)?;
+        let elem_len = T::len(&elem_layout);
+        N.checked_mul(elem_len).ok_or(ContigError::Overflow)?;
+        Ok(ArrayLayout {
+            elem_layout,
+            elem_len,
+        })
+    }
+
+    fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        ArrayConstView {
+            base: buf,
+            elem_layout: layout.elem_layout.clone(),
+            elem_len: layout.elem_len,
+        }
+    }
+
+    fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        ArrayMutView {
+            base: buf,
+            elem_layout: layout.elem_layout.clone(),
+            elem_len: layout.elem_len,
+        }
+    }
+}
+
+// Arrays of static elements are themselves static.
+impl<F, T, const N: usize> ContigStatic<F> for [T; N]
+where
+    T: ContigStatic<F> + 'static,
+    T::Layout: Clone + 'static,
+{
+    const LEN: usize = N * T::LEN;
+    const LAYOUT: Self::Layout = ArrayLayout {
+        elem_layout: T::LAYOUT,
+        elem_len: T::LEN,
+    };
+
+    fn view_static<'a>(buf: &'a [F]) -> Self::ConstView<'a>
+    where
+        F: 'a,
+    {
+        debug_assert!(buf.len() >= Self::LEN);
+        ArrayConstView {
+            base: buf,
+            elem_layout: T::LAYOUT,
+            elem_len: T::LEN,
+        }
+    }
+
+    fn view_static_mut<'a>(buf: &'a mut [F]) -> Self::MutView<'a>
+    where
+        F: 'a,
+    {
+        debug_assert!(buf.len() >= Self::LEN);
+        ArrayMutView {
+            base: buf,
+            elem_layout: T::LAYOUT,
+            elem_len: T::LEN,
+        }
+    }
+}
+
+impl<F, T, const N: usize> ContigValue<F> for [T; N]
+where
+    T: ContigValue<F> + 'static,
+    T::Config: PartialEq,
+    T::Layout: Clone + 'static,
+{
+    type Value = [T::Value; N];
+
+    fn config_of(value: &Self::Value) -> Self::Config {
+        Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_config_of(value: &Self::Value) -> Result<Self::Config, ContigError> {
+        elem_config_of::<F, T>(value)
+    }
+
+    fn empty_config() -> Self::Config {
+        T::empty_config()
+    }
+
+    fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+        pack_elems::<F, T>(value, &layout.elem_layout, layout.elem_len, buf);
+    }
+
+    fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+        core::array::from_fn(|i| {
+            let elem = &buf[i * layout.elem_len..(i + 1) * layout.elem_len];
+            T::unpack(&layout.elem_layout, elem)
+        })
+    }
+}
+
+// ---------- Ragged<[T]> (per-element configs) ----------
+
+/// Marker type representing a runtime-sized sequence of `T` values that are each sized by
+/// their own config (e.g. links with differing numbers of collision spheres).
+pub struct Ragged<T: ?Sized>(PhantomData<T>);
+
+/// Fully computed layout information for a ragged array of `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaggedLayout<TLayout> {
+    /// Layout metadata of each element, in order.
+    pub elem_layouts: Vec<TLayout>,
+    /// Scalar range of each element relative to the start of the array.
+    pub offsets: Vec<Range<usize>>,
+    /// Total scalar footprint of all elements.
+    pub len: usize,
+}
+
+impl<TLayout> RaggedLayout<TLayout> {
+    /// Number of elements contained in this layout.
+    pub fn count(&self) -> usize {
+        self.elem_layouts.len()
+    }
+}
+
+/// Immutable view into a ragged run of `T` elements.
+pub struct RaggedConstView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+{
+    base: &'a [F],
+    layout: &'a RaggedLayout<T::Layout>,
+}
+
+/// Mutable view into a ragged run of `T` elements.
+pub struct RaggedMutView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+{
+    base: &'a mut [F],
+    layout: &'a RaggedLayout<T::Layout>,
+}
+
+impl<'a, F, T> RaggedConstView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+{
+    #[inline]
+    /// Number of elements contained in this view.
+    pub fn len(&self) -> usize {
+        self.layout.count()
+    }
+    #[inline]
+    /// Whether this view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.layout.count() == 0
+    }
+    #[inline]
+    /// Fetch a read-only view for element `i` (panics if out of bounds).
+    pub fn get(&self, i: usize) -> T::ConstView<'_> {
+        let range = self.layout.offsets[i].clone();
+        T::view(&self.layout.elem_layouts[i], &self.base[range])
+    }
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> impl Iterator<Item = T::ConstView<'_>> {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+}
+
+impl<'a, F, T> RaggedMutView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+{
+    #[inline]
+    /// Number of elements contained in this view.
+    pub fn len(&self) -> usize {
+        self.layout.count()
+    }
+    #[inline]
+    /// Whether this view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.layout.count() == 0
+    }
+    #[inline]
+    /// Fetch a mutable view for element `i` (panics if out of bounds).
+    pub fn get_mut(&mut self, i: usize) -> T::MutView<'_> {
+        let range = self.layout.offsets[i].clone();
+        T::view_mut(&self.layout.elem_layouts[i], &mut self.base[range])
+    }
+    #[inline]
+    /// Fetch a read-only view for element `i` (panics if out of bounds).
+    pub fn get(&self, i: usize) -> T::ConstView<'_> {
+        let range = self.layout.offsets[i].clone();
+        T::view(&self.layout.elem_layouts[i], &self.base[range])
+    }
+    /// Iterate over read-only views of every element in order.
+    pub fn iter(&self) -> impl Iterator<Item = T::ConstView<'_>> {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+    /// Iterate over mutable views of every element in order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = T::MutView<'_>> {
+        let layout = self.layout;
+        let mut rest = &mut *self.base;
+        let mut pos = 0;
+        // Offsets are ordered and disjoint, so each element is peeled off the front.
+        layout
+            .elem_layouts
+            .iter()
+            .zip(&layout.offsets)
+            .map(move |(elem_layout, range)| {
+                let (_, tail) = core::mem::take(&mut rest).split_at_mut(range.start - pos);
+                let (head, tail) = tail.split_at_mut(range.len());
+                rest = tail;
+                pos = range.end;
+                T::view_mut(elem_layout, head)
+            })
+    }
+}
+
+impl<'a, F, T> core::fmt::Debug for RaggedConstView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, F, T> core::fmt::Debug for RaggedMutView<'a, F, T>
+where
+    T: Contig<F>,
+    T::Layout: 'a,
+    for<'b> T::ConstView<'b>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Ragged array adapter: one config and layout per element, packed back to back.
+impl<F, T> Contig<F> for Ragged<[T]>
+where
+    T: Contig<F> + 'static,
+    T::Layout: 'static,
+{
+    type Config = Vec<T::Config>;
+    type Layout = RaggedLayout<T::Layout>;
+    type ConstView<'a>
+        = RaggedConstView<'a, F, T>
+    where
+        F: 'a;
+    type MutView<'a>
+        = RaggedMutView<'a, F, T>
+    where
+        F: 'a;
+
+    fn layout(config: &Self::Config) -> Self::Layout {
+        let elem_layouts: Vec<T::Layout> = config.iter().map(T::layout).collect();
+        let mut cursor = TakeCursor::new();
+        let offsets = elem_layouts
+            .iter()
+            .map(|elem_layout| cursor.take_range(T::len(elem_layout)))
+            .collect();
+        RaggedLayout {
+            elem_layouts,
+            offsets,
+            len: cursor.finish(),
+        }
+    }
+
+    fn len(layout: &Self::Layout) -> usize {
+        layout.len
+    }
+
+    fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+        let children = layout
+            .elem_layouts
+            .iter()
+            .zip(&layout.offsets)
+            .enumerate()
+            .map(|(i, (elem_layout, range))| {
+                T::schema(elem_layout, offset + range.start).with_segment(Segment::Index(i))
+            })
+            .collect();
+        SchemaNode::new(SchemaKind::Array, offset..offset + layout.len)
+            .with_children(children)
+            .with_adapter("Ragged")
+    }
+
+    fn copy_overlap(src_layout: &Self::Layout, src: &[F], dst_layout: &Self::Layout, dst: &mut [F])
+    where
+        F: Clone,
+    {
+        let src_elems = src_layout.elem_layouts.iter().zip(&src_layout.offsets);
+        let dst_elems = dst_layout.elem_layouts.iter().zip(&dst_layout.offsets);
+        for ((src_elem, src_range), (dst_elem, dst_range)) in src_elems.zip(dst_elems) {
+            T::copy_overlap(
+                src_elem,
+                &src[src_range.clone()],
+                dst_elem,
+                &mut dst[dst_range.clone()],
+            );
+        }
+    }
+
+    fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
+        debug_assert!(buf.len() >= layout.len);
+        RaggedConstView { base: buf, layout }
+    }
+
+    fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
+        debug_assert!(buf.len() >= layout.len);
+        RaggedMutView { base: buf, layout }
+    }
+
+    fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+        let elem_layouts = config
+            .iter()
+            .map(T::try_layout)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut cursor = TakeCursor::new();
+        let offsets = elem_layouts
+            .iter()
+            .map(|elem_layout| cursor.try_take_range(T::len(elem_layout)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RaggedLayout {
+            elem_layouts,
+            offsets,
+            len: cursor.finish(),
+        })
+    }
+}
+
+impl<F, T> ContigValue<F> for Ragged<[T]>
+where
+    T: ContigValue<F> + 'static,
+    T::Layout: 'static,
+{
+    type Value = Vec<T::Value>;
+
+    fn config_of(value: &Self::Value) -> Self::Config {
+        Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_config_of(value: &Self::Value) -> Result<Self::Config, ContigError> {
+        let configs = value.iter().enumerate();
+        configs
+            .map(|(i, value)| T::try_config_of(value).map_err(|err| err.within(Segment::Index(i))))
+            .collect()
+    }
+
+    fn empty_config() -> Self::Config {
+        Vec::new()
+    }
+
+    fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+        assert_eq!(
+            value.len(),
+            layout.count(),
+            "value length does not match layout"
+        );
+        let elems = layout.elem_layouts.iter().zip(&layout.offsets);
+        for (value, (elem_layout, range)) in value.iter().zip(elems) {
+            T::pack(value, elem_layout, &mut buf[range.clone()]);
+        }
+    }
+
+    fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+        let elems = layout.elem_layouts.iter().zip(&layout.offsets);
+        elems
+            .map(|(elem_layout, range)| T::unpack(elem_layout, &buf[range.clone()]))
+            .collect()
+    }
+}
+
+// ---------- Opt<T> (optional values) ----------
+
+/// Marker type representing a `T` that is present or absent depending on the config, and
+/// occupies no scalars when absent.
+pub struct Opt<T: ?Sized>(PhantomData<T>);
+
+/// Config and layout of an [`Opt`]: either absent, or present with the inner `T`'s config
+/// or layout.
+///
+/// Unlike `Option` this keeps presence through serialization, where `Some(())` and `None`
+/// would both become JSON `null`; convert to and from `Option` with `From`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Presence<T> {
+    /// The value is left out and spans no scalars.
+    #[default]
+    Absent,
+    /// The value is laid out as described by the inner config or layout.
+    Present(T),
+}
+
+impl<T> Presence<T> {
+    /// Borrow the inner config or layout, if present.
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Presence::Absent => None,
+            Presence::Present(inner) => Some(inner),
+        }
+    }
+    /// Whether the value is laid out.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Presence::Present(_))
+    }
+}
+
+impl<T> From<Option<T>> for Presence<T> {
+    fn from(option: Option<T>) -> Self {
+        option.map_or(Presence::Absent, Presence::Present)
+    }
+}
+
+impl<T> From<Presence<T>> for Option<T> {
+    fn from(presence: Presence<T>) -> Self {
+        match presence {
+            Presence::Absent => None,
+            Presence::Present(inner) => Some(inner),
+        }
+    }
+}
+
+// Optional adapter: a present config lays out a `T`, an absent one spans nothing.
+impl<F, T> Contig<F> for Opt<T>
+where
+    T: Contig<F> + 'static,
+    T::Layout: 'static,
+{
+    type Config = Presence<T::Config>;
+    type Layout = Presence<T::Layout>;
+    type ConstView<'a>
+        = Option<T::ConstView<'a>>
+    where
+        F: 'a;
+    type MutView<'a>
+        = Option<T::MutView<'a>>
+    where
+        F: 'a;
+
+    fn layout(config: &Self::Config) -> Self::Layout {
+        config.as_option().map(T::layout).into()
+    }
+
+    fn len(layout: &Self::Layout) -> usize {
+        layout.as_option().map_or(0, T::len)
+    }
+
+    fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+        match layout {
+            Presence::Present(layout) => T::schema(layout, offset).with_adapter("Opt"),
+            Presence::Absent => {
+                SchemaNode::new(SchemaKind::Absent, offset..offset).with_adapter("Opt")
+            }
+        }
+    }
+
+    fn copy_overlap(src_layout: &Self::Layout, src: &[F], dst_layout: &Self::Layout, dst: &mut [F])
+    where
+        F: Clone,
+    {
+        if let (Presence::Present(src_layout), Presence::Present(dst_layout)) =
+            (src_layout, dst_layout)
+        {
+            T::copy_overlap(src_layout, src, dst_layout, dst);
+        }
+    }
+
+    fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        layout.as_option().map(|layout| T::view(layout, buf))
+    }
+
+    fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
+        debug_assert!(buf.len() >= Self::len(layout));
+        layout.as_option().map(|layout| T::view_mut(layout, buf))
+    }
+
+    fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+        Ok(config.as_option().map(T::try_layout).transpose()?.into())
+    }
+}
+
+impl<F, T> ContigValue<F> for Opt<T>
+where
+    T: ContigValue<F> + 'static,
+    T::Layout: 'static,
+{
+    type Value = Option<T::Value>;
+
+    fn config_of(value: &Self::Value) -> Self::Config {
+        Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_config_of(value: &Self::Value) -> Result<Self::Config, ContigError> {
+        Ok(value.as_ref().map(T::try_config_of).transpose()?.into())
+    }
+
+    fn empty_config() -> Self::Config {
+        Presence::Absent
+    }
+
+    fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+        match (value, layout) {
+            (Some(value), Presence::Present(layout)) => T::pack(value, layout, buf),
+            (None, Presence::Absent) => {}
+            _ => panic!("value presence does not match layout"),
+        }
+    }
+
+    fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+        layout.as_option().map(|layout| T::unpack(layout, buf))
+    }
+}
+
+// ---------- Owned container ----------
+
+/// Owns a layout together with the buffer it describes, so state can be stored in structs
+/// or sent across threads without borrowing between the two.
+///
+/// Derefs to the underlying scalar slice; typed access goes through [`view`](Self::view)
+/// and [`view_mut`](Self::view_mut).
+pub struct ContigBox<F, T>
+where
+    T: Contig<F>,
+{
+    layout: T::Layout,
+    buf: Vec<F>,
+}
+
+impl<F, T> ContigBox<F, T>
+where
+    T: Contig<F>,
+{
+    /// Compute the layout for `config` and allocate a zero-initialized (`F::default()`)
+    /// buffer for it.
+    pub fn new(config: &T::Config) -> Self
+    where
+        F: Clone + Default,
+    {
+        Self::from_layout(T::layout(config))
+    }
+    /// Like [`new`](Self::new), reporting layout errors instead of panicking.
+    pub fn try_new(config: &T::Config) -> Result<Self, ContigError>
+    where
+        F: Clone + Default,
+    {
+        Ok(Self::from_layout(T::try_layout(config)?))
+    }
+    /// Allocate a default-initialized buffer for an existing layout.
+    pub fn from_layout(layout: T::Layout) -> Self
+    where
+        F: Clone + Default,
+    {
+        let buf = vec![F::default(); T::len(&layout)];
+        Self { layout, buf }
+    }
+    /// Pair a layout with an existing buffer, failing unless the buffer holds exactly the
+    /// scalars the layout spans.
+    pub fn from_parts(layout: T::Layout, buf: Vec<F>) -> Result<Self, ContigError> {
+        let expected = T::len(&layout);
+        ContigError::check_len(expected, buf.len())?;
+        if buf.len() > expected {
+            return Err(ContigError::BufferTooLong {
+                expected,
+                actual: buf.len(),
+            });
+        }
+        Ok(Self { layout, buf })
+    }
+    /// Split back into the layout and the buffer.
+    pub fn into_parts(self) -> (T::Layout, Vec<F>) {
+        (self.layout, self.buf)
+    }
+    /// Switch to the layout for `config`, keeping the data both layouts have in common
+    /// (see [`relayout`]).
+    pub fn relayout(&mut self, config: &T::Config)
+    where
+        F: Clone + Default,
+    {
+        let (layout, buf) = relayout::<F, T>(&self.layout, &self.buf, config);
+        self.layout = layout;
+        self.buf = buf;
+    }
+    #[inline]
+    /// Layout metadata describing the buffer.
+    pub fn layout(&self) -> &T::Layout {
+        &self.layout
+    }
+    #[inline]
+    /// Borrow a read-only view over the whole value.
+    pub fn view(&self) -> T::ConstView<'_> {
+        T::view(&self.layout, &self.buf)
+    }
+    #[inline]
+    /// Borrow a mutable view over the whole value.
+    pub fn view_mut(&mut self) -> T::MutView<'_> {
+        T::view_mut(&self.layout, &mut self.buf)
+    }
+    #[inline]
+    /// Expose the underlying scalars.
+    pub fn as_slice(&self) -> &[F] {
+        &self.buf
+    }
+    #[inline]
+    /// Expose the underlying scalars mutably.
+    pub fn as_mut_slice(&mut self) -> &mut [F] {
+        &mut self.buf
+    }
+}
+
+impl<F, T> ContigBox<F, T>
+where
+    T: ContigValue<F>,
+{
+    /// Lay out and pack an owned value.
+    pub fn from_value(value: &T::Value) -> Self
+    where
+        F: Clone + Default,
+    {
+        let mut this = Self::new(&T::config_of(value));
+        T::pack(value, &this.layout, &mut this.buf);
+        this
+    }
+    /// Unpack the buffer into an owned value.
+    pub fn to_value(&self) -> T::Value {
+        T::unpack(&self.layout, &self.buf)
+    }
+}
+
+impl<F, T> Clone for ContigBox<F, T>
+where
+    T: Contig<F>,
+    T::Layout: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            layout: self.layout.clone(),
+            buf: self.buf.clone(),
+        }
+    }
+}
+
+impl<F, T> core::ops::Deref for ContigBox<F, T>
+where
+    T: Contig<F>,
+{
+    type Target = [F];
+
+    fn deref(&self) -> &[F] {
+        &self.buf
+    }
+}
+
+impl<F, T> core::ops::DerefMut for ContigBox<F, T>
+where
+    T: Contig<F>,
+{
+    fn deref_mut(&mut self) -> &mut [F] {
+        &mut self.buf
+    }
+}
+
+// ---------- Relayout ----------
+
+/// Lay out `T` for `new_config` and carry over the data `old_buf` (laid out by
+/// `old_layout`) has in common with it, field by field; new slots hold `F::default()`.
+///
+/// Existing `Dyn` elements keep their values when the array grows or shrinks.
+pub fn relayout<F, T>(
+    old_layout: &T::Layout,
+    old_buf: &[F],
+    new_config: &T::Config,
+) -> (T::Layout, Vec<F>)
+where
+    T: Contig<F>,
+    F: Clone + Default,
+{
+    let layout = T::layout(new_config);
+    let mut buf = vec![F::default(); T::len(&layout)];
+    T::copy_overlap(old_layout, old_buf, &layout, &mut buf);
+    (layout, buf)
+}
+
+// ---------- Migration ----------
+
+/// What [`migrate`] carried over between two differently shaped layouts.
+///
+/// Paths use the dotted form of [`SchemaNode::flat_names`] and name the outermost field
+/// or element that appeared or disappeared as a whole (`imu_bias`, `links.2`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Number of scalars copied from the old buffer.
+    pub kept: usize,
+    /// Paths only the new layout has; their scalars hold `F::default()`.
+    pub added: Vec<String>,
+    /// Paths only the old layout had; their scalars were dropped.
+    pub removed: Vec<String>,
+}
+
+impl MigrationReport {
+    /// Whether every scalar matched by name in both directions.
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl core::fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "kept {} scalars", self.kept)?;
+        if !self.added.is_empty() {
+            write!(f, "; added {}", self.added.join(", "))?;
+        }
+        if !self.removed.is_empty() {
+            write!(f, "; removed {}", self.removed.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lay out `T` for `new_config` and fill it from `old_buf` by matching scalar paths:
+/// `old_names` holds one [`flat_names`](Contig::flat_names) entry per scalar of the old
+/// buffer, typically saved alongside it by an older build.
+///
+/// Scalars whose path exists on both sides are copied wherever they moved to; new paths
+/// hold `F::default()` and vanished ones are dropped, as listed in the report. Enum paths
+/// name the active variant, so fields never carry over from one variant to another. Unlike
+/// [`relayout`], the old layout may belong to a different (e.g. since edited) type.
+pub fn migrate<F, T>(
+    old_names: &[String],
+    old_buf: &[F],
+    new_config: &T::Config,
+) -> (T::Layout, Vec<F>, MigrationReport)
+where
+    T: Contig<F>,
+    F: Clone + Default,
+{
+    assert!(
+        old_buf.len() >= old_names.len(),
+        "old buffer is shorter than its name list"
+    );
+    let layout = T::layout(new_config);
+    let new_names = T::flat_names(&layout);
+    let old_index: std::collections::HashMap<&str, usize> = old_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), i))
+        .collect();
+
+    let mut buf = vec![F::default(); new_names.len()];
+    let mut old_used = vec![false; old_names.len()];
+    let mut new_found = vec![false; new_names.len()];
+    for (i, name) in new_names.iter().enumerate() {
+        if let Some(&j) = old_index.get(name.as_str()) {
+            buf[i] = old_buf[j].clone();
+            old_used[j] = true;
+            new_found[i] = true;
+        }
+    }
+    let report = MigrationReport {
+        kept: new_found.iter().filter(|found| **found).count(),
+        added: unmatched_paths(&new_names, &new_found),
+        removed: unmatched_paths(old_names, &old_used),
+    };
+    (layout, buf, report)
+}
+
+/// Shortest dotted prefixes covering exactly the unmatched names, in buffer order.
+fn unmatched_paths(names: &[String], matched: &[bool]) -> Vec<String> {
+    fn prefixes(name: &str) -> impl Iterator<Item = &str> {
+        name.match_indices('.')
+            .map(move |(end, _)| &name[..end])
+            .chain(core::iter::once(name))
+    }
+
+    // (scalars under the prefix, unmatched scalars under it)
+    let mut counts = std::collections::HashMap::<&str, (usize, usize)>::new();
+    for (name, matched) in names.iter().zip(matched) {
+        for prefix in prefixes(name) {
+            let entry = counts.entry(prefix).or_default();
+            entry.0 += 1;
+            entry.1 += usize::from(!matched);
+        }
+    }
+    let mut paths: Vec<String> = Vec::new();
+    for (name, _) in names.iter().zip(matched).filter(|(_, matched)| !**matched) {
+        let path = prefixes(name)
+            .find(|prefix| counts[prefix].0 == counts[prefix].1)
+            .unwrap_or(name);
+        if paths.last().map(String::as_str) != Some(path) {
+            paths.push(path.to_string());
+        }
+    }
+    paths
+}
+
+// ---------- Element iterators ----------
+
+/// Iterator yielding read-only views over consecutive, equally sized `T` elements.
+pub struct ElemIter<'a, F, T>
+where
+    T: Contig<F>,
+{
+    rest: &'a [F],
+    remaining: usize,
+    elem_layout: &'a T::Layout,
+    elem_len: usize,
+}
+
+impl<'a, F, T> ElemIter<'a, F, T>
+where
+    T: Contig<F>,
+{
+    fn new(base: &'a [F], count: usize, elem_layout: &'a T::Layout, elem_len: usize) -> Self {
+        Self {
+            rest: base,
+            remaining: count,
+            elem_layout,
+            elem_len,
+        }
+    }
+}
+
+impl<'a, F, T> Iterator for ElemIter<'a, F, T>
+where
+    T: Contig<F>,
+{
+    type Item = T::ConstView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (head, tail) = self.rest.split_at(self.elem_len);
+        self.rest = tail;
+        self.remaining -= 1;
+        Some(T::view(self.elem_layout, head))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, F, T> ExactSizeIterator for ElemIter<'a, F, T> where T: Contig<F> {}
+
+/// Iterator yielding mutable views over consecutive, equally sized `T` elements.
+pub struct ElemIterMut<'a, F, T>
+where
+    T: Contig<F>,
+{
+    rest: &'a mut [F],
+    remaining: usize,
+    elem_layout: &'a T::Layout,
+    elem_len: usize,
+}
+
+impl<'a, F, T> ElemIterMut<'a, F, T>
+where
+    T: Contig<F>,
+{
+    fn new(base: &'a mut [F], count: usize, elem_layout: &'a T::Layout, elem_len: usize) -> Self {
+        Self {
+            rest: base,
+            remaining: count,
+            elem_layout,
+            elem_len,
+        }
+    }
+}
+
+impl<'a, F, T> Iterator for ElemIterMut<'a, F, T>
+where
+    T: Contig<F>,
+{
+    type Item = T::MutView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (head, tail) = core::mem::take(&mut self.rest).split_at_mut(self.elem_len);
+        self.rest = tail;
+        self.remaining -= 1;
+        Some(T::view_mut(self.elem_layout, head))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, F, T> ExactSizeIterator for ElemIterMut<'a, F, T> where T: Contig<F> {}
+
+// ---------- Structured serialization (serde) ----------
+
+#[cfg(feature = "serde")]
+pub use structured::{DeserializeView, SchemaSeed, SchemaSerialize};
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use structured::{FieldSeeds, ViewSeed, deserialize_variant};
+
+#[cfg(feature = "serde")]
+/// Schema-driven `Serialize`/`DeserializeSeed` for buffers, and `Serialize` and
+/// [`DeserializeView`] for array views.
+mod structured {
+    use super::*;
+    use serde::Deserialize;
+    use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeMap, Serializer};
+
+    /// Serializes the scalars a [`SchemaNode`] covers as structured data.
+    ///
+    /// Structs become maps keyed by field name, enums single-entry maps from the variant
+    /// name to its fields, arrays sequences, matrices sequences of rows, and absent `Opt`s
+    /// `None`; present `Opt`s serialize as their value.
+    pub struct SchemaSerialize<'a, F> {
+        node: &'a SchemaNode,
+        buf: &'a [F],
+    }
+
+    impl<'a, F> SchemaSerialize<'a, F> {
+        /// Serialize `node` over `buf`, whose index 0 is scalar 0 of the node's ranges.
+        pub fn new(node: &'a SchemaNode, buf: &'a [F]) -> Self {
+            Self { node, buf }
+        }
+
+        fn child(&self, node: &'a SchemaNode) -> Self {
+            Self {
+                node,
+                buf: self.buf,
+            }
+        }
+    }
+
+    /// Name of a struct or variant field node.
+    fn field_name(node: &SchemaNode) -> &'static str {
+        match node.segment {
+            Some(Segment::Field(name)) => name,
+            _ => "",
+        }
+    }
+
+    /// The fields of a struct or enum node as a map.
+    struct Fields<'s, 'a, F>(&'s SchemaSerialize<'a, F>);
+
+    impl<F: Serialize> Serialize for Fields<'_, '_, F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let children = self.0.node.children.iter();
+            serializer.collect_map(children.map(|node| (field_name(node), self.0.child(node))))
+        }
+    }
+
+    impl<F: Serialize> Serialize for SchemaSerialize<'_, F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let Range { start, end } = self.node.range;
+            match self.node.kind {
+                SchemaKind::Scalar => self.buf[start].serialize(serializer),
+                SchemaKind::Opaque if end - start == 1 => self.buf[start].serialize(serializer),
+                SchemaKind::Opaque => serializer.collect_seq(&self.buf[start..end]),
+                SchemaKind::Struct => Fields(self).serialize(serializer),
+                SchemaKind::Enum { variant } => {
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry(variant, &Fields(self))?;
+                    map.end()
+                }
+                SchemaKind::Array => {
+                    serializer.collect_seq(self.node.children.iter().map(|node| self.child(node)))
+                }
+                SchemaKind::Matrix { rows, cols } => {
+                    serializer.collect_seq((0..rows).map(|r| MatrixRow {
+                        buf: &self.buf[start + r..end],
+                        rows,
+                        cols,
+                    }))
+                }
+                SchemaKind::Absent => serializer.serialize_none(),
+            }
+        }
+    }
+
+    /// One row of a column-major matrix; `buf` starts at the row's first entry.
+    struct MatrixRow<'a, F> {
+        buf: &'a [F],
+        rows: usize,
+        cols: usize,
+    }
+
+    impl<F: Serialize> Serialize for MatrixRow<'_, F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq((0..self.cols).map(|c| &self.buf[c * self.rows]))
+        }
+    }
+
+    /// Deserializes the structured form written by [`SchemaSerialize`] straight into the
+    /// scalars a [`SchemaNode`] covers, rejecting data whose shape disagrees with the layout.
+    pub struct SchemaSeed<'a, F> {
+        node: &'a SchemaNode,
+        buf: &'a mut [F],
+    }
+
+    impl<'a, F> SchemaSeed<'a, F> {
+        /// Write `node` into `buf`, whose index 0 is scalar 0 of the node's ranges.
+        pub fn new(node: &'a SchemaNode, buf: &'a mut [F]) -> Self {
+            Self { node, buf }
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>> DeserializeSeed<'de> for SchemaSeed<'_, F> {
+        type Value = ();
+
+        fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+            let Range { start, end } = self.node.range;
+            match self.node.kind {
+                SchemaKind::Scalar => self.buf[start] = F::deserialize(deserializer)?,
+                SchemaKind::Opaque if end - start == 1 => {
+                    self.buf[start] = F::deserialize(deserializer)?
+                }
+                SchemaKind::Opaque => deserializer.deserialize_seq(ScalarsVisitor {
+                    buf: &mut self.buf[start..end],
+                    stride: 1,
+                })?,
+                SchemaKind::Struct => deserializer.deserialize_map(FieldsVisitor(self))?,
+                SchemaKind::Enum { variant } => deserializer.deserialize_map(VariantVisitor {
+                    variant,
+                    fields: self,
+                })?,
+                SchemaKind::Array => deserializer.deserialize_seq(ArrayVisitor(self))?,
+                SchemaKind::Matrix { .. } => deserializer.deserialize_seq(MatrixVisitor(self))?,
+                SchemaKind::Absent => absent(deserializer)?,
+            }
+            Ok(())
+        }
+    }
+
+    /// Accept only `None`, the form an absent `Opt` serializes as.
+    fn absent<'de, D: de::Deserializer<'de>>(deserializer: D) -> Result<(), D::Error> {
+        match Option::<IgnoredAny>::deserialize(deserializer)? {
+            Some(_) => Err(de::Error::custom("value given for an absent field")),
+            None => Ok(()),
+        }
+    }
+
+    /// Expected length in a length mismatch error, e.g. "3 elements".
+    struct Count(usize, &'static str);
+
+    impl de::Expected for Count {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{} {}", self.0, self.1)
+        }
+    }
+
+    /// Named fields that read their values in place; implemented over the children of a
+    /// struct or enum node, and by the field views of generated enum views.
+    pub trait FieldSeeds<'de> {
+        /// Name of field `index`, or `None` past the last field.
+        fn name(&self, index: usize) -> Option<&'static str>;
+
+        /// Read the next value of `map` into field `index`.
+        fn next_value<A: MapAccess<'de>>(
+            &mut self,
+            index: usize,
+            map: &mut A,
+        ) -> Result<(), A::Error>;
+    }
+
+    impl<'de, F: Deserialize<'de>> FieldSeeds<'de> for SchemaSeed<'_, F> {
+        fn name(&self, index: usize) -> Option<&'static str> {
+            self.node.children.get(index).map(field_name)
+        }
+
+        fn next_value<A: MapAccess<'de>>(
+            &mut self,
+            index: usize,
+            map: &mut A,
+        ) -> Result<(), A::Error> {
+            map.next_value_seed(SchemaSeed::new(&self.node.children[index], &mut *self.buf))
+        }
+    }
+
+    /// A variant without fields.
+    impl<'de> FieldSeeds<'de> for () {
+        fn name(&self, _: usize) -> Option<&'static str> {
+            None
+        }
+
+        fn next_value<A: MapAccess<'de>>(&mut self, _: usize, _: &mut A) -> Result<(), A::Error> {
+            unreachable!("a variant without fields has no values to read")
+        }
+    }
+
+    /// Reads a struct's (or variant's) fields by name, each exactly once.
+    struct FieldsVisitor<S>(S);
+
+    impl<'de, S: FieldSeeds<'de>> Visitor<'de> for FieldsVisitor<S> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("a map of field names to values")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+            let names: Vec<_> = (0..).map_while(|i| self.0.name(i)).collect();
+            let mut seen = vec![false; names.len()];
+            while let Some(key) = map.next_key::<String>()? {
+                let Some(i) = names.iter().position(|&name| name == key) else {
+                    return Err(de::Error::custom(format_args!("unknown field `{}`", key)));
+                };
+                if core::mem::replace(&mut seen[i], true) {
+                    return Err(de::Error::duplicate_field(names[i]));
+                }
+                self.0.next_value(i, &mut map)?;
+            }
+            match seen.iter().position(|seen| !seen) {
+                Some(i) => Err(de::Error::missing_field(names[i])),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Reads the single `{ variant: { fields } }` entry of the variant the layout selects.
+    struct VariantVisitor<S> {
+        variant: &'static str,
+        fields: S,
+    }
+
+    impl<'de, S: FieldSeeds<'de>> Visitor<'de> for VariantVisitor<S> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("a single-entry map from the variant name to its fields")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+            let key = map
+                .next_key::<String>()?
+                .ok_or_else(|| de::Error::invalid_length(0, &Count(1, "entry")))?;
+            if key != self.variant {
+                return Err(de::Error::custom(format_args!(
+                    "variant `{}` does not match layout variant `{}`",
+                    key, self.variant
+                )));
+            }
+            map.next_value_seed(FieldsSeed(self.fields))?;
+            match map.next_key::<IgnoredAny>()? {
+                Some(_) => Err(de::Error::invalid_length(2, &Count(1, "entry"))),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// A variant's fields, read as a map like a struct.
+    struct FieldsSeed<S>(S);
+
+    impl<'de, S: FieldSeeds<'de>> DeserializeSeed<'de> for FieldsSeed<S> {
+        type Value = ();
+
+        fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+            deserializer.deserialize_map(FieldsVisitor(self.0))
+        }
+    }
+
+    /// Read `{ variant: { fields } }` into the field views of the active `variant`; the
+    /// `deserialize_from` that `contig-derive` generates for enum views calls this.
+    pub fn deserialize_variant<'de, D, S>(
+        deserializer: D,
+        variant: &'static str,
+        fields: S,
+    ) -> Result<(), D::Error>
+    where
+        D: de::Deserializer<'de>,
+        S: FieldSeeds<'de>,
+    {
+        deserializer.deserialize_map(VariantVisitor { variant, fields })
+    }
+
+    /// Mutable views that overwrite their scalars in place from the structured form their
+    /// `Serialize` impl writes, failing if the shapes differ.
+    ///
+    /// Array views and derived views implement it; enum views read each field of the active
+    /// variant through it.
+    pub trait DeserializeView<'de> {
+        /// Overwrite the viewed scalars with the value `deserializer` yields.
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error>;
+    }
+
+    impl<'de, F: Deserialize<'de>> DeserializeView<'de> for &mut F {
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            **self = F::deserialize(deserializer)?;
+            Ok(())
+        }
+    }
+
+    impl<'de, V: DeserializeView<'de>> DeserializeView<'de> for Option<V> {
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            match self {
+                Some(view) => view.deserialize_from(deserializer),
+                None => absent(deserializer),
+            }
+        }
+    }
+
+    /// Seed reading one value into a [`DeserializeView`].
+    pub struct ViewSeed<'a, V>(pub &'a mut V);
+
+    impl<'de, V: DeserializeView<'de>> DeserializeSeed<'de> for ViewSeed<'_, V> {
+        type Value = ();
+
+        fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+            self.0.deserialize_from(deserializer)
+        }
+    }
+
+    /// Reads exactly one element per child of an array node.
+    struct ArrayVisitor<'a, F>(SchemaSeed<'a, F>);
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for ArrayVisitor<'_, F> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "a sequence of {} elements", self.0.node.children.len())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+            let SchemaSeed { node, buf } = self.0;
+            let expected = &Count(node.children.len(), "elements");
+            for (i, child) in node.children.iter().enumerate() {
+                if seq
+                    .next_element_seed(SchemaSeed::new(child, &mut *buf))?
+                    .is_none()
+                {
+                    return Err(de::Error::invalid_length(i, expected));
+                }
+            }
+            match seq.next_element::<IgnoredAny>()? {
+                Some(_) => Err(de::Error::invalid_length(node.children.len() + 1, expected)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Reads a matrix as `rows` sequences of `cols` scalars.
+    struct MatrixVisitor<'a, F>(SchemaSeed<'a, F>);
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for MatrixVisitor<'_, F> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("a sequence of matrix rows")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+            let SchemaKind::Matrix { rows, .. } = self.0.node.kind else {
+                unreachable!("matrix visitor over a non-matrix node")
+            };
+            let Range { start, end } = self.0.node.range;
+            let expected = &Count(rows, "rows");
+            for r in 0..rows {
+                let row = ScalarsVisitor {
+                    buf: &mut self.0.buf[start + r..end],
+                    stride: rows,
+                };
+                if seq.next_element_seed(row)?.is_none() {
+                    return Err(de::Error::invalid_length(r, expected));
+                }
+            }
+            match seq.next_element::<IgnoredAny>()? {
+                Some(_) => Err(de::Error::invalid_length(rows + 1, expected)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Reads scalars into every `stride`-th slot of `buf`, filling all of them.
+    struct ScalarsVisitor<'a, F> {
+        buf: &'a mut [F],
+        stride: usize,
+    }
+
+    impl<'de, F: Deserialize<'de>> DeserializeSeed<'de> for ScalarsVisitor<'_, F> {
+        type Value = ();
+
+        fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for ScalarsVisitor<'_, F> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let count = self.buf.len().div_ceil(self.stride);
+            write!(f, "a sequence of {} scalars", count)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+            let count = self.buf.len().div_ceil(self.stride);
+            let expected = &Count(count, "scalars");
+            for i in 0..count {
+                match seq.next_element()? {
+                    Some(value) => self.buf[i * self.stride] = value,
+                    None => return Err(de::Error::invalid_length(i, expected)),
+                }
+            }
+            match seq.next_element::<IgnoredAny>()? {
+                Some(_) => Err(de::Error::invalid_length(count + 1, expected)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    // Array views serialize through the schema of their elements, so nested structs keep
+    // their field names and matrices their rows.
+    impl<F: Serialize, T> Serialize for DynArrayConstView<'_, F, T>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, self.count, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<F: Serialize, T> Serialize for DynArrayMutView<'_, F, T>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, self.count, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<F: Serialize, T, const N: usize> Serialize for ArrayConstView<'_, F, T, N>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, N, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<F: Serialize, T, const N: usize> Serialize for ArrayMutView<'_, F, T, N>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, N, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<F: Serialize, T> Serialize for RaggedConstView<'_, F, T>
+    where
+        T: Contig<F> + 'static,
+        T::Layout: 'static,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = <Ragged<[T]> as Contig<F>>::schema(self.layout, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<F: Serialize, T> Serialize for RaggedMutView<'_, F, T>
+    where
+        T: Contig<F> + 'static,
+        T::Layout: 'static,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let schema = <Ragged<[T]> as Contig<F>>::schema(self.layout, 0);
+            SchemaSerialize::new(&schema, self.base).serialize(serializer)
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>, T> DeserializeView<'de> for DynArrayMutView<'_, F, T>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, self.count, 0);
+            SchemaSeed::new(&schema, self.base).deserialize(deserializer)
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>, T, const N: usize> DeserializeView<'de> for ArrayMutView<'_, F, T, N>
+    where
+        T: Contig<F>,
+        T::Layout: Clone,
+    {
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            let schema = array_schema::<F, T>(&self.elem_layout, self.elem_len, N, 0);
+            SchemaSeed::new(&schema, self.base).deserialize(deserializer)
+        }
+    }
+
+    impl<'de, F: Deserialize<'de>, T> DeserializeView<'de> for RaggedMutView<'_, F, T>
+    where
+        T: Contig<F> + 'static,
+        T::Layout: 'static,
+    {
+        fn deserialize_from<D: de::Deserializer<'de>>(
+            &mut self,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            let schema = <Ragged<[T]> as Contig<F>>::schema(self.layout, 0);
+            SchemaSeed::new(&schema, self.base).deserialize(deserializer)
+        }
+    }
+}
+
+// ---------- Snapshots ----------
+
+#[cfg(feature = "snapshot")]
+/// Versioned, self-describing files holding a config and the buffer laid out for it.
+///
+/// A snapshot is the magic bytes [`MAGIC`](snapshot::MAGIC), a little-endian `u32` format
+/// [`VERSION`](snapshot::VERSION), a little-endian `u64` header length, a JSON header, and
+/// the raw scalars. The header records the scalar type and byte order, the scalar count,
+/// the serialized config and the layout built from it, the layout's schema tree, and the
+/// path of every scalar. [`load`](snapshot::load) rebuilds the layout from the stored
+/// config and refuses the file unless it matches the stored layout, spans the stored scalar
+/// count, and has the same schema, so reordered or retyped fields are reported instead of
+/// silently misread; lengths in the file are checked against the bytes actually present,
+/// so a corrupt or forged file fails instead of exhausting memory.
+/// [`load_migrated`](snapshot::load_migrated) instead maps the stored scalars into a new
+/// config by path (see [`migrate`]).
+pub mod snapshot {
+    use super::*;
+    use serde::{Deserialize, Serialize, de::DeserializeOwned};
+    use std::io::{Read, Write};
+
+    /// Leading bytes of every snapshot.
+    pub const MAGIC: [u8; 8] = *b"CONTIGSN";
+    /// Format version written by [`save`] and accepted by [`load`].
+    pub const VERSION: u32 = 1;
+
+    /// Scalars that can be stored in a snapshot as raw bytes; implemented for the scalars
+    /// that implement [`Contig`] (`f32` and `f64`).
+    pub trait SnapshotScalar: Copy {
+        /// Type name recorded in the header and checked on load.
+        const NAME: &'static str;
+        /// Encoded size in bytes.
+        const SIZE: usize;
+        /// Write the native-endian bytes of `self` into `out` (`SIZE` bytes long).
+        fn write_bytes(self, out: &mut [u8]);
+        /// Decode `SIZE` bytes stored with the given byte order.
+        fn read_bytes(bytes: &[u8], little_endian: bool) -> Self;
+    }
+
+    macro_rules! impl_snapshot_scalar {
+        ($($t:ty),* $(,)?) => {
+            $(
+                impl SnapshotScalar for $t {
+                    const NAME: &'static str = stringify!($t);
+                    const SIZE: usize = core::mem::size_of::<$t>();
+
+                    fn write_bytes(self, out: &mut [u8]) {
+                        out.copy_from_slice(&self.to_ne_bytes());
+                    }
+
+                    fn read_bytes(bytes: &[u8], little_endian: bool) -> Self {
+                        let bytes = bytes.try_into().expect("scalar byte width");
+                        if little_endian {
+                            <$t>::from_le_bytes(bytes)
+                        } else {
+                            <$t>::from_be_bytes(bytes)
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_snapshot_scalar!(f32, f64);
+
+    /// Failure while writing or reading a snapshot.
+    #[derive(Debug)]
+    pub enum SnapshotError {
+        /// The underlying reader or writer failed (including files cut short).
+        Io(std::io::Error),
+        /// The config given to [`save`] has no valid layout or does not fit the buffer.
+        Contig(ContigError),
+        /// The input does not start with [`MAGIC`].
+        BadMagic,
+        /// The file was written by a format version this build cannot read.
+        UnsupportedVersion(u32),
+        /// The header is not valid JSON of the expected shape.
+        Header(String),
+        /// The file stores a different scalar type than requested.
+        ScalarMismatch {
+            /// Scalar type requested by the caller.
+            expected: &'static str,
+            /// Scalar type recorded in the file.
+            found: String,
+        },
+        /// The stored config cannot be decoded as a config of the requested type, or its
+        /// layout differs from the stored one or spans a different number of scalars than
+        /// the file stores.
+        Layout(String),
+        /// The stored layout matches, but the requested type arranges it differently.
+        SchemaMismatch {
+            /// Schema tree of the requested type for the stored layout.
+            expected: String,
+            /// Schema tree recorded in the file.
+            found: String,
+        },
+    }
+
+    impl core::fmt::Display for SnapshotError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                SnapshotError::Io(err) => write!(f, "snapshot i/o failed: {}", err),
+                SnapshotError::Contig(err) => write!(f, "{}", err),
+                SnapshotError::BadMagic => f.write_str("not a contig snapshot"),
+                SnapshotError::UnsupportedVersion(version) => {
+                    write!(f, "unsupported snapshot version {}", version)
+                }
+                SnapshotError::Header(reason) => write!(f, "invalid snapshot header: {}", reason),
+                SnapshotError::ScalarMismatch { expected, found } => write!(
+                    f,
+                    "snapshot holds `{}` scalars, expected `{}`",
+                    found, expected
+                ),
+                SnapshotError::Layout(reason) => {
+                    write!(f, "snapshot layout does not fit this type: {}", reason)
+                }
+                SnapshotError::SchemaMismatch { expected, found } => write!(
+                    f,
+                    "snapshot schema does not match this type:\nstored:\n{}expected:\n{}",
+                    found, expected
+                ),
+            }
+        }
+    }
+
+    impl std::error::Error for SnapshotError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                SnapshotError::Io(err) => Some(err),
+                SnapshotError::Contig(err) => Some(err),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<std::io::Error> for SnapshotError {
+        fn from(err: std::io::Error) -> Self {
+            SnapshotError::Io(err)
+        }
+    }
+
+    impl From<ContigError> for SnapshotError {
+        fn from(err: ContigError) -> Self {
+            SnapshotError::Contig(err)
+        }
+    }
+
+    /// JSON header; `config` is decoded only after the scalar checks pass, and `layout` is
+    /// only compared against the layout rebuilt from it.
+    #[derive(Serialize, Deserialize)]
+    struct Header<C, L> {
+        scalar: String,
+        endian: String,
+        len: usize,
+        schema: String,
+        #[serde(default)]
+        names: Vec<String>,
+        config: C,
+        layout: L,
+    }
+
+    const NATIVE_ENDIAN: &str = if cfg!(target_endian = "little") {
+        "little"
+    } else {
+        "big"
+    };
+
+    /// Write `config` and its layout together with the first scalars of `buf`, as many as
+    /// that layout spans.
+    pub fn save<F, T, W>(config: &T::Config, buf: &[F], writer: W) -> Result<(), SnapshotError>
+    where
+        F: SnapshotScalar,
+        T: Contig<F>,
+        T::Config: Serialize,
+        T::Layout: Serialize,
+        W: Write,
+    {
+        write::<F, T, W>(config, &T::try_layout(config)?, buf, writer)
+    }
+
+    /// Write a snapshot of `layout`, which was built from `config`.
+    fn write<F, T, W>(
+        config: &T::Config,
+        layout: &T::Layout,
+        buf: &[F],
+        mut writer: W,
+    ) -> Result<(), SnapshotError>
+    where
+        F: SnapshotScalar,
+        T: Contig<F>,
+        T::Config: Serialize,
+        T::Layout: Serialize,
+        W: Write,
+    {
+        let len = T::len(layout);
+        ContigError::check_len(len, buf.len())?;
+        let schema = T::schema(layout, 0);
+        let header = Header {
+            scalar: F::NAME.to_string(),
+            endian: NATIVE_ENDIAN.to_string(),
+            len,
+            schema: schema.to_string(),
+            names: schema.flat_names(),
+            config,
+            layout,
+        };
+        let header =
+            serde_json::to_vec(&header).map_err(|e| SnapshotError::Header(e.to_string()))?;
+        let mut data = vec![0u8; len * F::SIZE];
+        for (value, out) in buf[..len].iter().zip(data.chunks_exact_mut(F::SIZE)) {
+            value.write_bytes(out);
+        }
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(header.len() as u64).to_le_bytes())?;
+        writer.write_all(&header)?;
+        writer.write_all(&data)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by [`save`] for the same scalar and a type whose schema is
+    /// unchanged, returning the layout of the stored config and its buffer.
+    pub fn load<F, T, R>(mut reader: R) -> Result<(T::Layout, Vec<F>), SnapshotError>
+    where
+        F: SnapshotScalar,
+        T: Contig<F>,
+        T::Config: DeserializeOwned,
+        T::Layout: Serialize,
+        R: Read,
+    {
+        let (header, little_endian) = read_header::<F, _>(&mut reader)?;
+        // Reading the scalars first bounds `header.len` by the bytes actually present, and
+        // the length check below bounds the layout before its schema tree is built.
+        let buf = read_scalars(&mut reader, header.len, little_endian)?;
+        let config: T::Config = serde_json::from_value(header.config)
+            .map_err(|e| SnapshotError::Layout(e.to_string()))?;
+        // Rebuilding the layout keeps its ranges consistent whatever the file claims; the
+        // stored one must still agree, so a tampered header is not silently reinterpreted.
+        let layout = T::try_layout(&config)?;
+        if T::len(&layout) != header.len {
+            return Err(SnapshotError::Layout(format!(
+                "layout spans {} scalars but the snapshot stores {}",
+                T::len(&layout),
+                header.len
+            )));
+        }
+        let schema = T::schema(&layout, 0).to_string();
+        if schema != header.schema {
+            return Err(SnapshotError::SchemaMismatch {
+                expected: schema,
+                found: header.schema,
+            });
+        }
+        let rebuilt =
+            serde_json::to_value(&layout).map_err(|e| SnapshotError::Layout(e.to_string()))?;
+        if rebuilt != header.layout {
+            return Err(SnapshotError::Layout(
+                "stored layout differs from the one its config builds".into(),
+            ));
+        }
+        Ok((layout, buf))
+    }
+
+    /// Read a snapshot written by any version of a type and map its scalars into the
+    /// layout of `config` by field path, reporting added and removed fields.
+    ///
+    /// Only the scalar type must match; the stored config and layout are not decoded.
+    pub fn load_migrated<F, T, R>(
+        mut reader: R,
+        config: &T::Config,
+    ) -> Result<(T::Layout, Vec<F>, MigrationReport), SnapshotError>
+    where
+        F: SnapshotScalar + Default,
+        T: Contig<F>,
+        R: Read,
+    {
+        let (header, little_endian) = read_header::<F, _>(&mut reader)?;
+        if header.names.len() != header.len {
+            return Err(SnapshotError::Header(
+                "scalar paths do not cover the buffer".into(),
+            ));
+        }
+        let old_buf = read_scalars::<F, _>(&mut reader, header.len, little_endian)?;
+        Ok(migrate::<F, T>(&header.names, &old_buf, config))
+    }
 
-    fn layout(config: &Self::Config) -> Self::Layout {
-        let elem_layout = T::layout(&config.elem);
-        let elem_len = T::len(&elem_layout);
-        DynArrayLayout {
-            len: config.len,
-            elem_layout,
-            elem_len,
+    /// Check the magic, version, and scalar type; returns the header and its byte order.
+    fn read_header<F: SnapshotScalar, R: Read>(
+        reader: &mut R,
+    ) -> Result<(Header<serde_json::Value, serde_json::Value>, bool), SnapshotError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let mut header_len = [0u8; 8];
+        reader.read_exact(&mut header_len)?;
+        let header = read_bytes(reader, u64::from_le_bytes(header_len))?;
+        let header: Header<serde_json::Value, serde_json::Value> =
+            serde_json::from_slice(&header).map_err(|e| SnapshotError::Header(e.to_string()))?;
+
+        if header.scalar != F::NAME {
+            return Err(SnapshotError::ScalarMismatch {
+                expected: F::NAME,
+                found: header.scalar,
+            });
         }
+        let little_endian = match header.endian.as_str() {
+            "little" => true,
+            "big" => false,
+            other => {
+                return Err(SnapshotError::Header(format!(
+                    "unknown byte order `{}`",
+                    other
+                )));
+            }
+        };
+        Ok((header, little_endian))
     }
 
-    fn len(layout: &Self::Layout) -> usize {
-        layout.len * layout.elem_len
+    /// Read the `len` raw scalars following the header.
+    fn read_scalars<F: SnapshotScalar, R: Read>(
+        reader: &mut R,
+        len: usize,
+        little_endian: bool,
+    ) -> Result<Vec<F>, SnapshotError> {
+        let size = len
+            .checked_mul(F::SIZE)
+            .ok_or(SnapshotError::Contig(ContigError::Overflow))?;
+        let data = read_bytes(reader, size as u64)?;
+        Ok(data
+            .chunks_exact(F::SIZE)
+            .map(|bytes| F::read_bytes(bytes, little_endian))
+            .collect())
     }
 
-    fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
-        debug_assert!(buf.len() >= Self::len(layout));
-        DynArrayConstView {
-            base: buf,
-            count: layout.len,
-            elem_layout: layout.elem_layout.clone(),
-            elem_len: layout.elem_len,
+    /// Read exactly `len` bytes, growing the buffer only as data arrives so that a length
+    /// read from the file cannot force a huge allocation up front.
+    fn read_bytes<R: Read>(reader: &mut R, len: u64) -> Result<Vec<u8>, SnapshotError> {
+        let mut data = Vec::new();
+        reader.take(len).read_to_end(&mut data)?;
+        if (data.len() as u64) < len {
+            return Err(SnapshotError::Io(std::io::ErrorKind::UnexpectedEof.into()));
         }
+        Ok(data)
     }
 
-    fn view_mut<'a>(layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
-        debug_assert!(buf.len() >= Self::len(layout));
-        DynArrayMutView {
-            base: buf,
-            count: layout.len,
-            elem_layout: layout.elem_layout.clone(),
-            elem_len: layout.elem_len,
+    impl<F, T> ContigBox<F, T>
+    where
+        F: SnapshotScalar,
+        T: Contig<F>,
+    {
+        /// Write this buffer as a snapshot of `config`, which must be the config the layout
+        /// was built from (see [`save`]).
+        pub fn save<W: Write>(&self, config: &T::Config, writer: W) -> Result<(), SnapshotError>
+        where
+            T::Config: Serialize,
+            T::Layout: Serialize,
+        {
+            let layout = T::try_layout(config)?;
+            if T::len(&layout) != T::len(&self.layout)
+                || T::schema_hash(&layout) != T::schema_hash(&self.layout)
+            {
+                return Err(ContigError::InvalidConfig {
+                    reason: "config does not describe this layout",
+                }
+                .into());
+            }
+            write::<F, T, W>(config, &self.layout, &self.buf, writer)
+        }
+
+        /// Read a snapshot written for this type (see [`load`]).
+        pub fn load<R: Read>(reader: R) -> Result<Self, SnapshotError>
+        where
+            T::Config: DeserializeOwned,
+            T::Layout: Serialize,
+        {
+            let (layout, buf) = load::<F, T, R>(reader)?;
+            Ok(Self { layout, buf })
         }
     }
 }
@@ -273,13 +3573,15 @@ pub mod na_types {
     use nalgebra as na;
 
     /// Configuration for a dynamic-column vector view.
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DynVectorConfig {
         /// Total number of elements in the vector.
         pub len: usize,
     }
     /// Layout metadata for a dynamic-column vector view.
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DynVectorLayout {
         /// Total number of elements in the vector.
         pub len: usize,
@@ -311,6 +3613,26 @@ pub mod na_types {
             layout.len
         }
 
+        fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+            let kind = SchemaKind::Matrix {
+                rows: layout.len,
+                cols: 1,
+            };
+            SchemaNode::new(kind, offset..offset + layout.len).with_adapter("NaDVector")
+        }
+
+        fn copy_overlap(
+            src_layout: &Self::Layout,
+            src: &[F],
+            dst_layout: &Self::Layout,
+            dst: &mut [F],
+        ) where
+            F: Clone,
+        {
+            let len = src_layout.len.min(dst_layout.len);
+            dst[..len].clone_from_slice(&src[..len]);
+        }
+
         fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
             debug_assert!(buf.len() >= layout.len);
             na::DVectorView::from_slice(buf, layout.len)
@@ -322,8 +3644,37 @@ pub mod na_types {
         }
     }
 
+    impl<F> ContigValue<F> for NaDVector<F>
+    where
+        F: na::Scalar,
+    {
+        type Value = na::DVector<F>;
+
+        fn config_of(value: &Self::Value) -> Self::Config {
+            DynVectorConfig { len: value.len() }
+        }
+
+        fn empty_config() -> Self::Config {
+            DynVectorConfig { len: 0 }
+        }
+
+        fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+            assert_eq!(
+                value.len(),
+                layout.len,
+                "value length does not match layout"
+            );
+            buf[..layout.len].clone_from_slice(value.as_slice());
+        }
+
+        fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+            na::DVector::from_column_slice(&buf[..layout.len])
+        }
+    }
+
     /// Configuration for a dynamic matrix view.
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DynMatrixConfig {
         /// Number of rows in the matrix.
         pub rows: usize,
@@ -331,7 +3682,8 @@ pub mod na_types {
         pub cols: usize,
     }
     /// Layout metadata for a dynamic matrix view.
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DynMatrixLayout {
         /// Number of rows in the matrix.
         pub rows: usize,
@@ -368,6 +3720,39 @@ pub mod na_types {
             layout.rows * layout.cols
         }
 
+        fn schema(layout: &Self::Layout, offset: usize) -> SchemaNode {
+            let kind = SchemaKind::Matrix {
+                rows: layout.rows,
+                cols: layout.cols,
+            };
+            SchemaNode::new(kind, offset..offset + Self::len(layout)).with_adapter("NaDMatrix")
+        }
+
+        fn copy_overlap(
+            src_layout: &Self::Layout,
+            src: &[F],
+            dst_layout: &Self::Layout,
+            dst: &mut [F],
+        ) where
+            F: Clone,
+        {
+            // Column-major: keep the top-left block both shapes share.
+            let rows = src_layout.rows.min(dst_layout.rows);
+            for col in 0..src_layout.cols.min(dst_layout.cols) {
+                let src_col = col * src_layout.rows;
+                let dst_col = col * dst_layout.rows;
+                dst[dst_col..dst_col + rows].clone_from_slice(&src[src_col..src_col + rows]);
+            }
+        }
+
+        fn try_layout(config: &Self::Config) -> Result<Self::Layout, ContigError> {
+            config
+                .rows
+                .checked_mul(config.cols)
+                .ok_or(ContigError::Overflow)?;
+            Ok(Self::layout(config))
+        }
+
         fn view<'a>(layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
             debug_assert!(buf.len() >= Self::len(layout));
             na::DMatrixView::from_slice_generic(buf, na::Dyn(layout.rows), na::Dyn(layout.cols))
@@ -378,16 +3763,149 @@ pub mod na_types {
             na::DMatrixViewMut::from_slice_generic(buf, na::Dyn(layout.rows), na::Dyn(layout.cols))
         }
     }
+
+    impl<F> ContigValue<F> for NaDMatrix<F>
+    where
+        F: na::Scalar,
+    {
+        type Value = na::DMatrix<F>;
+
+        fn config_of(value: &Self::Value) -> Self::Config {
+            DynMatrixConfig {
+                rows: value.nrows(),
+                cols: value.ncols(),
+            }
+        }
+
+        fn empty_config() -> Self::Config {
+            DynMatrixConfig { rows: 0, cols: 0 }
+        }
+
+        fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [F]) {
+            assert_eq!(
+                value.shape(),
+                (layout.rows, layout.cols),
+                "value shape does not match layout"
+            );
+            buf[..value.len()].clone_from_slice(value.as_slice());
+        }
+
+        fn unpack(layout: &Self::Layout, buf: &[F]) -> Self::Value {
+            let len = Self::len(layout);
+            na::DMatrix::from_column_slice(layout.rows, layout.cols, &buf[..len])
+        }
+    }
+
+    /// Layout metadata for a statically sized matrix view; it carries no additional information.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SMatrixLayout;
+
+    /// Marker type that adapts `nalgebra::SMatrix<F, R, C>` to [`Contig`] and [`ContigStatic`].
+    pub struct NaSMatrix<F, const R: usize, const C: usize>(PhantomData<F>);
+
+    /// Marker type that adapts `nalgebra::SVector<F, D>` to [`Contig`] and [`ContigStatic`].
+    pub type NaSVector<F, const D: usize> = NaSMatrix<F, D, 1>;
+
+    impl<F, const R: usize, const C: usize> Contig<F> for NaSMatrix<F, R, C>
+    where
+        F: na::Scalar,
+    {
+        type Config = ();
+        type Layout = SMatrixLayout;
+        type ConstView<'a>
+            = na::SMatrixView<'a, F, R, C>
+        where
+            F: 'a;
+        type MutView<'a>
+            = na::SMatrixViewMut<'a, F, R, C>
+        where
+            F: 'a;
+
+        fn layout(_config: &Self::Config) -> Self::Layout {
+            SMatrixLayout
+        }
+
+        fn len(_layout: &Self::Layout) -> usize {
+            R * C
+        }
+
+        fn schema(_layout: &Self::Layout, offset: usize) -> SchemaNode {
+            let kind = SchemaKind::Matrix { rows: R, cols: C };
+            SchemaNode::new(kind, offset..offset + R * C).with_adapter("NaSMatrix")
+        }
+
+        fn view<'a>(_layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
+            debug_assert!(buf.len() >= R * C);
+            na::SMatrixView::from_slice_generic(&buf[..R * C], na::Const::<R>, na::Const::<C>)
+        }
+
+        fn view_mut<'a>(_layout: &'a Self::Layout, buf: &'a mut [F]) -> Self::MutView<'a> {
+            debug_assert!(buf.len() >= R * C);
+            na::SMatrixViewMut::from_slice_generic(
+                &mut buf[..R * C],
+                na::Const::<R>,
+                na::Const::<C>,
+            )
+        }
+    }
+
+    impl<F, const R: usize, const C: usize> ContigStatic<F> for NaSMatrix<F, R, C>
+    where
+        F: na::Scalar,
+    {
+        const LEN: usize = R * C;
+        const LAYOUT: Self::Layout = SMatrixLayout;
+
+        fn view_static<'a>(buf: &'a [F]) -> Self::ConstView<'a>
+        where
+            F: 'a,
+        {
+            Self::view(&SMatrixLayout, buf)
+        }
+
+        fn view_static_mut<'a>(buf: &'a mut [F]) -> Self::MutView<'a>
+        where
+            F: 'a,
+        {
+            Self::view_mut(&SMatrixLayout, buf)
+        }
+    }
+
+    impl<F, const R: usize, const C: usize> ContigValue<F> for NaSMatrix<F, R, C>
+    where
+        F: na::Scalar,
+    {
+        type Value = na::SMatrix<F, R, C>;
+
+        fn config_of(_value: &Self::Value) -> Self::Config {}
+
+        fn empty_config() -> Self::Config {}
+
+        fn pack(value: &Self::Value, _layout: &Self::Layout, buf: &mut [F]) {
+            buf[..R * C].clone_from_slice(value.as_slice());
+        }
+
+        fn unpack(_layout: &Self::Layout, buf: &[F]) -> Self::Value {
+            na::SMatrix::from_column_slice(&buf[..R * C])
+        }
+    }
 }
 
 // ---------- Prelude ----------
 
 /// Convenience re-exports for building `contig`-based layouts.
 pub mod prelude {
+    #[cfg(feature = "serde")]
+    pub use super::DeserializeView;
     #[cfg(feature = "nalgebra")]
     pub use super::na_types::*;
     pub use super::{
-        Contig, Dyn, DynArrayConfig, DynArrayConstView, DynArrayLayout, DynArrayMutView, TakeCursor,
+        ArrayConstView, ArrayLayout, ArrayMutView, Contig, ContigBox, ContigError, ContigStatic,
+        ContigValue, Dyn, DynArrayConfig, DynArrayConstView, DynArrayLayout, DynArrayMutView,
+        ElemIter, ElemIterMut, MigrationReport, Opt, Presence, Ragged, RaggedConstView,
+        RaggedLayout, RaggedMutView, Real, SchemaKind, SchemaNode, Segment, TakeCursor, migrate,
+        relayout,
     };
 }
 
@@ -544,6 +4062,524 @@ mod tests {
         assert_eq!(*z1, 6.0);
     }
 
+    #[test]
+    fn fixed_array_of_scalars() {
+        let layout = <[f64; 4]>::layout(&());
+        assert_eq!(<[f64; 4]>::len(&layout), 4);
+        let mut buf = [0.0f64; 4];
+
+        {
+            let mut view = <[f64; 4]>::view_mut(&layout, &mut buf);
+            assert_eq!(view.len(), 4);
+            for (i, value) in view.iter_mut().enumerate() {
+                *value = i as f64 + 1.0;
+            }
+            *view.get_mut(3) = 40.0;
+        }
+
+        let view = <[f64; 4]>::view(&layout, &buf);
+        let values: Vec<f64> = view.iter().copied().collect();
+        assert_eq!(values, [1.0, 2.0, 3.0, 40.0]);
+        assert_eq!(*view.get(0), 1.0);
+    }
+
+    #[test]
+    fn fixed_array_of_triples() {
+        let layout = <[Triple<f64>; 2]>::layout(&());
+        assert_eq!(<[Triple<f64>; 2]>::len(&layout), 6);
+        let mut buf = [0.0f64; 6];
+
+        {
+            let mut view = <[Triple<f64>; 2]>::view_mut(&layout, &mut buf);
+            view.get_mut(0).set(1.0, 2.0, 3.0);
+            view.get_mut(1).set(4.0, 5.0, 6.0);
+        }
+
+        assert_eq!(buf, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let view = <[Triple<f64>; 2]>::view(&layout, &buf);
+        let xs: Vec<f64> = view.iter().map(|t| *t.components().0).collect();
+        assert_eq!(xs, [1.0, 4.0]);
+    }
+
+    #[test]
+    fn static_array_views_without_layout() {
+        type Grid = [[f64; 2]; 3];
+        assert_eq!(<Grid as ContigStatic<f64>>::LEN, 6);
+        assert_eq!(Grid::len(&Grid::LAYOUT), 6);
+
+        let mut buf = [0.0f64; <Grid as ContigStatic<f64>>::LEN];
+        {
+            let mut view = Grid::view_static_mut(&mut buf);
+            *view.get_mut(2).get_mut(1) = 7.0;
+        }
+        assert_eq!(buf[5], 7.0);
+        let view = Grid::view_static(&buf);
+        assert_eq!(*view.get(2).get(1), 7.0);
+    }
+
+    #[test]
+    fn dyn_array_iteration_and_slicing() {
+        let cfg = DynArrayConfig { len: 5, elem: () };
+        let layout = Dyn::<[f64]>::layout(&cfg);
+        let mut buf = vec![0.0f64; 6];
+
+        {
+            let mut view = Dyn::<[f64]>::view_mut(&layout, &mut buf);
+            for (i, value) in view.iter_mut().enumerate() {
+                *value = i as f64;
+            }
+            for mut chunk in view.chunks_mut(2) {
+                *chunk.get_mut(0) += 10.0;
+            }
+            let (mut head, mut tail) = view.split_at_mut(3);
+            *head.get_mut(2) += *tail.get_mut(0);
+            assert_eq!(tail.len(), 2);
+            *view.slice_mut(4..).get_mut(0) = -1.0;
+        }
+        // The scalar past the array footprint is never touched.
+        assert_eq!(buf, [10.0, 1.0, 15.0, 3.0, -1.0, 0.0]);
+
+        let view = Dyn::<[f64]>::view(&layout, &buf);
+        let values: Vec<f64> = view.slice(1..=3).iter().copied().collect();
+        assert_eq!(values, [1.0, 15.0, 3.0]);
+        let lens: Vec<usize> = view.chunks(2).map(|chunk| chunk.len()).collect();
+        assert_eq!(lens, [2, 2, 1]);
+        let diffs: Vec<f64> = view.pairs().map(|(a, b)| *b - *a).collect();
+        assert_eq!(diffs, [-9.0, 14.0, -12.0, -4.0]);
+        let (head, tail) = view.split_at(5);
+        assert_eq!((head.len(), tail.len()), (5, 0));
+    }
+
+    #[test]
+    fn dyn_array_get_many_mut_borrows_disjoint_elements() {
+        let cfg = DynArrayConfig { len: 3, elem: () };
+        let layout = Dyn::<[Triple<f64>]>::layout(&cfg);
+        let mut buf = vec![0.0f64; 9];
+        let mut view = Dyn::<[Triple<f64>]>::view_mut(&layout, &mut buf);
+
+        for k in 0..view.len() - 1 {
+            let [mut a, mut b] = view.get_many_mut([k, k + 1]).unwrap();
+            a.set(k as f64, 0.0, 0.0);
+            b.set(0.0, k as f64 + 1.0, 0.0);
+        }
+        assert!(view.get_many_mut([1, 1]).is_none());
+        assert!(view.get_many_mut([0, 3]).is_none());
+        assert_eq!(buf, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn fallible_layout_and_views_report_errors() {
+        let mut cursor = TakeCursor::new();
+        assert_eq!(cursor.try_take_range(3), Ok(0..3));
+        assert_eq!(
+            cursor.try_take_range(usize::MAX),
+            Err(ContigError::Overflow)
+        );
+
+        let huge = DynArrayConfig {
+            len: usize::MAX,
+            elem: (),
+        };
+        assert!(matches!(
+            Dyn::<[Triple<f64>]>::try_layout(&huge),
+            Err(ContigError::Overflow)
+        ));
+
+        let layout = <[Triple<f64>; 2]>::try_layout(&()).unwrap();
+        let mut buf = [0.0f64; 5];
+        assert!(matches!(
+            <[Triple<f64>; 2]>::try_view_mut(&layout, &mut buf),
+            Err(ContigError::BufferTooShort {
+                expected: 6,
+                actual: 5
+            })
+        ));
+        let buf = [0.0f64; 6];
+        assert_eq!(
+            <[Triple<f64>; 2]>::try_view(&layout, &buf).unwrap().len(),
+            2
+        );
+        assert_eq!(
+            ContigError::BufferTooShort {
+                expected: 6,
+                actual: 5
+            }
+            .to_string(),
+            "buffer too short for layout: expected 6 scalars, got 5"
+        );
+    }
+
+    #[test]
+    fn ragged_array_of_dyn_arrays() {
+        type Spheres = Ragged<[Dyn<[Triple<f64>]>]>;
+        let cfg = vec![
+            DynArrayConfig { len: 1, elem: () },
+            DynArrayConfig { len: 0, elem: () },
+            DynArrayConfig { len: 2, elem: () },
+        ];
+        let layout = Spheres::layout(&cfg);
+        assert_eq!(Spheres::len(&layout), 9);
+        assert_eq!(layout.offsets, [0..3, 3..3, 3..9]);
+        let mut buf = vec![0.0f64; 9];
+
+        {
+            let mut view = Spheres::view_mut(&layout, &mut buf);
+            assert_eq!(view.len(), 3);
+            view.get_mut(2).get_mut(1).set(7.0, 8.0, 9.0);
+            for (i, mut link) in view.iter_mut().enumerate() {
+                if !link.is_empty() {
+                    link.get_mut(0).set(i as f64, 0.0, 0.0);
+                }
+            }
+        }
+
+        assert_eq!(buf, [0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 7.0, 8.0, 9.0]);
+        let view = Spheres::view(&layout, &buf);
+        let counts: Vec<usize> = view.iter().map(|link| link.len()).collect();
+        assert_eq!(counts, [1, 0, 2]);
+        assert_eq!(*view.get(2).get(1).components().2, 9.0);
+    }
+
+    #[test]
+    fn optional_value_spans_nothing_when_absent() {
+        let absent = Opt::<Triple<f64>>::layout(&Presence::Absent);
+        assert_eq!(Opt::<Triple<f64>>::len(&absent), 0);
+        assert!(Opt::<Triple<f64>>::view(&absent, &[]).is_none());
+
+        let present = Opt::<Triple<f64>>::layout(&Presence::Present(()));
+        assert_eq!(Opt::<Triple<f64>>::len(&present), 3);
+        let mut buf = [0.0f64; 3];
+        if let Some(mut bias) = Opt::<Triple<f64>>::view_mut(&present, &mut buf) {
+            bias.set(0.1, 0.2, 0.3);
+        }
+        let view = Opt::<Triple<f64>>::view(&present, &buf).unwrap();
+        assert_eq!(*view.components().1, 0.2);
+    }
+
+    #[test]
+    fn schema_tree_of_nested_adapters() {
+        type Nested = Dyn<[Opt<[f64; 2]>]>;
+        let cfg = DynArrayConfig {
+            len: 2,
+            elem: Presence::Present(()),
+        };
+        let layout = Nested::layout(&cfg);
+        let schema = Nested::schema(&layout, 5);
+        assert_eq!(schema.kind, SchemaKind::Array);
+        assert_eq!(schema.range, 5..9);
+        let second = &schema.children[1];
+        assert_eq!(second.segment, Some(Segment::Index(1)));
+        assert_eq!(second.children[1].range, 8..9);
+        assert_eq!(schema.iter().filter(|node| node.is_leaf()).count(), 4);
+        assert_eq!(
+            schema.to_string(),
+            "array (Dyn) 5..9\n  [0]: array (Opt [T; N]) 5..7\n    [0]: scalar 5..6\n    \
+             [1]: scalar 6..7\n  [1]: array (Opt [T; N]) 7..9\n    [0]: scalar 7..8\n    \
+             [1]: scalar 8..9\n"
+        );
+
+        let absent = Opt::<f64>::schema(&Presence::Absent, 3);
+        assert_eq!(absent.kind, SchemaKind::Absent);
+        assert!(absent.range.is_empty());
+
+        let ragged = Ragged::<[Triple<f64>]>::layout(&vec![(), ()]);
+        let schema = Ragged::<[Triple<f64>]>::schema(&ragged, 0);
+        assert_eq!(schema.children[1].kind, SchemaKind::Opaque);
+        assert_eq!(schema.children[1].range, 3..6);
+    }
+
+    #[test]
+    fn path_of_flat_index() {
+        type Nested = Dyn<[Opt<[f64; 2]>]>;
+        let cfg = DynArrayConfig {
+            len: 2,
+            elem: Presence::Present(()),
+        };
+        let layout = Nested::layout(&cfg);
+        assert_eq!(Nested::path_of(&layout, 3).as_deref(), Some("[1][1]"));
+        assert_eq!(Nested::path_of(&layout, 4), None);
+
+        let layout = Dyn::<[Triple<f64>]>::layout(&DynArrayConfig { len: 2, elem: () });
+        assert_eq!(
+            Dyn::<[Triple<f64>]>::path_of(&layout, 4).as_deref(),
+            Some("[1][1]")
+        );
+        assert_eq!(f64::path_of(&ScalarLayout, 0).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn range_of_resolves_paths() {
+        type Nested = Dyn<[Opt<[Triple<f64>; 2]>]>;
+        let cfg = DynArrayConfig {
+            len: 2,
+            elem: Presence::Present(()),
+        };
+        let layout = Nested::layout(&cfg);
+        assert_eq!(Nested::range_of(&layout, ""), Ok(0..12));
+        assert_eq!(Nested::range_of(&layout, "[1]"), Ok(6..12));
+        assert_eq!(Nested::range_of(&layout, "[1][0][2]"), Ok(8..9));
+        for index in 0..12 {
+            let path = Nested::path_of(&layout, index).unwrap();
+            assert_eq!(Nested::range_of(&layout, &path), Ok(index..index + 1));
+        }
+
+        let reason = |path| match Nested::range_of(&layout, path) {
+            Err(ContigError::InvalidPath { reason, .. }) => reason,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(reason("[2]"), "index out of bounds");
+        assert_eq!(reason("[0][0][1][0]"), "path continues past a scalar");
+        assert_eq!(reason("[0].x"), "no such field");
+        assert_eq!(reason("[0][(0, 1)]"), "entry of a non-matrix");
+        assert_eq!(reason("[0"), "unclosed `[`");
+
+        let mut buf = vec![0.0f64; 12];
+        let schema = Nested::schema(&layout, 0);
+        schema.get_path_mut(&mut buf, "[1][1]").unwrap()[0] = 5.0;
+        assert_eq!(schema.get_path(&buf, "[1][1][0]"), Ok(&[5.0][..]));
+        assert_eq!(
+            schema.get_path(&buf[..4], "[1]"),
+            Err(ContigError::BufferTooShort {
+                expected: 12,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn flat_names_in_buffer_order() {
+        type Nested = Dyn<[Opt<[Triple<f64>; 1]>]>;
+        let cfg = DynArrayConfig {
+            len: 2,
+            elem: Presence::Present(()),
+        };
+        let layout = Nested::layout(&cfg);
+        let names = Nested::flat_names(&layout);
+        assert_eq!(
+            names,
+            ["0.0.0", "0.0.1", "0.0.2", "1.0.0", "1.0.1", "1.0.2"]
+        );
+        assert_eq!(f64::flat_names(&ScalarLayout), [""]);
+    }
+
+    #[test]
+    fn migrate_matches_scalars_by_path() {
+        let old_layout = Dyn::<[[f64; 2]]>::layout(&DynArrayConfig { len: 3, elem: () });
+        let old_names = Dyn::<[[f64; 2]]>::flat_names(&old_layout);
+        let old_buf = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let cfg = DynArrayConfig { len: 2, elem: () };
+        let (layout, buf, report) = migrate::<f64, Dyn<[[f64; 3]]>>(&old_names, &old_buf, &cfg);
+        assert_eq!(layout.len, 2);
+        assert_eq!(buf, [0.0, 1.0, 0.0, 2.0, 3.0, 0.0]);
+        assert_eq!(report.kept, 4);
+        assert_eq!(report.added, ["0.2", "1.2"]);
+        assert_eq!(report.removed, ["2"]);
+        assert_eq!(
+            report.to_string(),
+            "kept 4 scalars; added 0.2, 1.2; removed 2"
+        );
+
+        let (_, same, report) = migrate::<f64, Dyn<[[f64; 2]]>>(
+            &old_names,
+            &old_buf,
+            &DynArrayConfig { len: 3, elem: () },
+        );
+        assert_eq!(same, old_buf);
+        assert!(report.is_unchanged());
+    }
+
+    #[test]
+    fn schema_hash_tracks_shape_and_scalar() {
+        type Pairs = Dyn<[[f64; 2]]>;
+        let layout = |len| Pairs::layout(&DynArrayConfig { len, elem: () });
+        assert_eq!(
+            Pairs::schema_hash(&layout(2)),
+            Pairs::schema_hash(&layout(2))
+        );
+        assert_ne!(
+            Pairs::schema_hash(&layout(2)),
+            Pairs::schema_hash(&layout(3))
+        );
+        assert_eq!(
+            Pairs::schema(&layout(2), 7).scalar_schema_hash::<f64>(),
+            Pairs::schema_hash(&layout(2))
+        );
+        // Same shape, different scalar.
+        assert_ne!(
+            <[f32; 2]>::schema_hash(&<[f32; 2]>::layout(&())),
+            <[f64; 2]>::schema_hash(&<[f64; 2]>::layout(&()))
+        );
+        assert_eq!(
+            Pairs::schema(&layout(2), 0).schema_hash(),
+            Dyn::<[[f32; 2]]>::schema(&layout(2), 0).schema_hash()
+        );
+        // Same footprint, different nesting.
+        let flat = <[f64; 4]>::layout(&());
+        assert_ne!(
+            <[f64; 4]>::schema_hash(&flat),
+            Pairs::schema_hash(&layout(2))
+        );
+        // Same shape, different adapters.
+        let dyn_pair = Dyn::<[f64]>::layout(&DynArrayConfig { len: 2, elem: () });
+        let ragged_pair = Ragged::<[f64]>::layout(&vec![(), ()]);
+        let hashes = [
+            Dyn::<[f64]>::schema_hash(&dyn_pair),
+            <[f64; 2]>::schema_hash(&<[f64; 2]>::layout(&())),
+            Ragged::<[f64]>::schema_hash(&ragged_pair),
+        ];
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[1], hashes[2]);
+        assert_ne!(
+            Opt::<f64>::schema_hash(&Presence::Present(ScalarLayout)),
+            f64::schema_hash(&ScalarLayout)
+        );
+        // Pinned so a change to the encoding shows up as a test failure.
+        assert_eq!(f64::schema_hash(&ScalarLayout), 0x6b21_dc77_c519_dd13);
+    }
+
+    #[test]
+    fn contig_box_owns_layout_and_buffer() {
+        let mut state =
+            ContigBox::<f64, Dyn<[Triple<f64>]>>::new(&DynArrayConfig { len: 2, elem: () });
+        assert_eq!(state.len(), 6);
+        state.view_mut().get_mut(1).set(1.0, 2.0, 3.0);
+        state[0] = -1.0;
+        assert_eq!(*state.view().get(1).components().2, 3.0);
+
+        // Owned state moves freely, e.g. across threads.
+        let state = std::thread::spawn(move || state).join().unwrap();
+        let copy = state.clone();
+        let (layout, buf) = state.into_parts();
+        assert_eq!(buf, [-1.0, 0.0, 0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(copy.as_slice(), buf.as_slice());
+        assert_eq!(
+            ContigBox::<f64, Dyn<[Triple<f64>]>>::from_parts(layout.clone(), vec![0.0; 5]).err(),
+            Some(ContigError::BufferTooShort {
+                expected: 6,
+                actual: 5
+            })
+        );
+        assert_eq!(
+            ContigBox::<f64, Dyn<[Triple<f64>]>>::from_parts(layout, vec![0.0; 7]).err(),
+            Some(ContigError::BufferTooLong {
+                expected: 6,
+                actual: 7
+            })
+        );
+    }
+
+    #[test]
+    fn relayout_preserves_overlapping_elements() {
+        type Knots = Dyn<[Opt<[f64; 2]>]>;
+        let old_cfg = DynArrayConfig {
+            len: 2,
+            elem: Presence::Present(()),
+        };
+        let old_layout = Knots::layout(&old_cfg);
+        let old_buf = [1.0, 2.0, 3.0, 4.0];
+
+        let grown = DynArrayConfig {
+            len: 3,
+            elem: Presence::Present(()),
+        };
+        let (layout, buf) = relayout::<f64, Knots>(&old_layout, &old_buf, &grown);
+        assert_eq!(Knots::len(&layout), 6);
+        assert_eq!(buf, [1.0, 2.0, 3.0, 4.0, 0.0, 0.0]);
+
+        let (_, buf) = relayout::<f64, Knots>(&layout, &buf, &DynArrayConfig { len: 1, ..grown });
+        assert_eq!(buf, [1.0, 2.0]);
+
+        let mut state = ContigBox::<f64, Ragged<[Dyn<[f64]>]>>::new(&vec![
+            DynArrayConfig { len: 1, elem: () },
+            DynArrayConfig { len: 2, elem: () },
+        ]);
+        state.copy_from_slice(&[1.0, 2.0, 3.0]);
+        state.relayout(&vec![
+            DynArrayConfig { len: 2, elem: () },
+            DynArrayConfig { len: 1, elem: () },
+            DynArrayConfig { len: 1, elem: () },
+        ]);
+        assert_eq!(state.as_slice(), [1.0, 0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn owned_values_roundtrip_through_buffers() {
+        type Knots = Ragged<[Dyn<[[f64; 2]]>]>;
+        let value = vec![vec![[1.0, 2.0]], vec![], vec![[3.0, 4.0], [5.0, 6.0]]];
+        let cfg = Knots::config_of(&value);
+        assert_eq!(cfg.iter().map(|c| c.len).collect::<Vec<_>>(), [1, 0, 2]);
+
+        let knots = ContigBox::<f64, Knots>::from_value(&value);
+        assert_eq!(knots.as_slice(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(knots.to_value(), value);
+
+        let mut bias = ContigBox::<f32, Opt<[f32; 3]>>::from_value(&Some([0.5; 3]));
+        bias.as_mut_slice()[1] = 1.5;
+        assert_eq!(bias.to_value(), Some([0.5, 1.5, 0.5]));
+        assert_eq!(Opt::<[f32; 3]>::config_of(&None), Presence::Absent);
+    }
+
+    #[test]
+    fn array_values_need_one_element_config() {
+        type Grid = Ragged<[Dyn<[Dyn<[f64]>]>]>;
+        let value = vec![vec![vec![1.0]], vec![vec![2.0], vec![3.0, 4.0]]];
+        assert_eq!(
+            Grid::try_config_of(&value),
+            Err(ContigError::MixedElements {
+                path: "[1]".into(),
+                index: 1
+            })
+        );
+        let empty = Dyn::<[Dyn<[f64]>]>::config_of(&Vec::new());
+        assert_eq!(empty, DynArrayConfig::default());
+
+        let err = Dyn::<[Opt<f64>]>::try_config_of(&vec![Some(1.0), Some(2.0), None]);
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "array elements 0 and 2 need different configs; use `Ragged` for per-element configs"
+        );
+    }
+
+    #[test]
+    fn dyn_array_vector_space_ops() {
+        type Points = Dyn<[[f64; 2]]>;
+        let layout = Points::layout(&DynArrayConfig { len: 2, elem: () });
+        let x0 = [1.0, 2.0, 3.0, 4.0];
+        let dx = [1.0, 2.0, 0.0, 0.0];
+        // Trailing scalars past the view's footprint stay untouched.
+        let mut x = [0.0, 0.0, 0.0, 0.0, 9.0];
+
+        let mut view = Points::view_mut(&layout, &mut x);
+        view.copy_from(&Points::view(&layout, &x0));
+        view.axpy(-1.0, &Points::view(&layout, &dx));
+        assert_eq!(view.norm(), 5.0);
+        assert_eq!(view.dot(&Points::view(&layout, &dx)), 0.0);
+        view.scale(2.0);
+        assert_eq!(view.as_const().norm(), 10.0);
+        view.slice_mut(1..).fill(-1.0);
+        assert_eq!(x, [0.0, 0.0, -1.0, -1.0, 9.0]);
+    }
+
+    #[test]
+    fn array_views_debug_as_lists() {
+        type Knots = Ragged<[Dyn<[[f64; 2]]>]>;
+        let layout = Knots::layout(&vec![
+            DynArrayConfig { len: 1, elem: () },
+            DynArrayConfig { len: 0, elem: () },
+        ]);
+        let mut buf = [1.0, 2.0];
+        assert_eq!(
+            format!("{:?}", Knots::view(&layout, &buf)),
+            "[[[1.0, 2.0]], []]"
+        );
+        assert_eq!(
+            format!("{:#?}", Knots::view_mut(&layout, &mut buf).get_mut(0)),
+            "[\n    [\n        1.0,\n        2.0,\n    ],\n]"
+        );
+    }
+
     #[test]
     fn dyn_array_zero_length_has_zero_footprint() {
         let cfg = DynArrayConfig { len: 0, elem: () };
@@ -574,4 +4610,11 @@ fn nalgebra_contig_vector_roundtrip() {
     for i in 0..view.len() {
         assert_eq!(view[i], i as f64);
     }
+
+    // A dynamic vector and a static column share a shape but not an adapter.
+    use crate::na_types::NaSMatrix;
+    assert_ne!(
+        NaDVector::<f64>::schema_hash(&layout),
+        NaSMatrix::<f64, 3, 1>::schema_hash(&crate::na_types::SMatrixLayout)
+    );
 }
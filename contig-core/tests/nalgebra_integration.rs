@@ -1,6 +1,6 @@
 #![cfg(feature = "nalgebra")]
 
-use contig_core::na_types::{DynMatrixConfig, NaDMatrix};
+use contig_core::na_types::{DynMatrixConfig, NaDMatrix, NaSMatrix, NaSVector};
 use contig_core::prelude::*;
 
 #[test]
@@ -26,3 +26,77 @@ fn nadmatrix_contig_roundtrip() {
         }
     }
 }
+
+#[test]
+fn nasmatrix_static_roundtrip() {
+    assert_eq!(<NaSMatrix<f64, 2, 3> as ContigStatic<f64>>::LEN, 6);
+    assert_eq!(<NaSVector<f64, 4> as ContigStatic<f64>>::LEN, 4);
+
+    let mut buf = [0.0f64; 6];
+    {
+        let mut view = NaSMatrix::<f64, 2, 3>::view_static_mut(&mut buf);
+        view[(1, 2)] = 5.0;
+    }
+    // nalgebra storage is column-major.
+    assert_eq!(buf[5], 5.0);
+    let view = NaSMatrix::<f64, 2, 3>::view_static(&buf);
+    assert_eq!(view[(1, 2)], 5.0);
+}
+
+#[test]
+fn nalgebra_schema_reports_matrix_shape() {
+    let layout = NaDMatrix::<f64>::layout(&DynMatrixConfig { rows: 2, cols: 3 });
+    let schema = NaDMatrix::<f64>::schema(&layout, 4);
+    assert_eq!(schema.kind, SchemaKind::Matrix { rows: 2, cols: 3 });
+    assert_eq!(schema.range, 4..10);
+    assert!(schema.is_leaf());
+
+    let schema = NaSVector::<f64, 3>::schema(&NaSVector::<f64, 3>::LAYOUT, 0);
+    assert_eq!(schema.kind, SchemaKind::Matrix { rows: 3, cols: 1 });
+}
+
+#[test]
+fn nalgebra_path_of_reports_entry() {
+    let layout = NaDMatrix::<f64>::layout(&DynMatrixConfig { rows: 2, cols: 3 });
+    // Column-major: index 3 is row 1 of column 1.
+    assert_eq!(
+        NaDMatrix::<f64>::path_of(&layout, 3).as_deref(),
+        Some("[(1, 1)]")
+    );
+    assert_eq!(NaDMatrix::<f64>::range_of(&layout, "[(1,2)]"), Ok(5..6));
+    assert!(NaDMatrix::<f64>::range_of(&layout, "[(2, 0)]").is_err());
+}
+
+#[test]
+fn nalgebra_flat_names_list_entries_column_major() {
+    let layout = NaDMatrix::<f64>::layout(&DynMatrixConfig { rows: 2, cols: 2 });
+    assert_eq!(
+        NaDMatrix::<f64>::flat_names(&layout),
+        ["(0,0)", "(1,0)", "(0,1)", "(1,1)"]
+    );
+}
+
+#[test]
+fn nalgebra_relayout_keeps_shared_block() {
+    let old_layout = NaDMatrix::<f64>::layout(&DynMatrixConfig { rows: 2, cols: 2 });
+    let old_buf = [1.0, 2.0, 3.0, 4.0];
+    let (layout, buf) = relayout::<f64, NaDMatrix<f64>>(
+        &old_layout,
+        &old_buf,
+        &DynMatrixConfig { rows: 3, cols: 1 },
+    );
+    assert_eq!(NaDMatrix::<f64>::len(&layout), 3);
+    assert_eq!(buf, [1.0, 2.0, 0.0]);
+}
+
+#[test]
+fn nalgebra_values_pack_and_unpack() {
+    let value = nalgebra::DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let matrix = ContigBox::<f64, NaDMatrix<f64>>::from_value(&value);
+    assert_eq!(matrix.as_slice(), [1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!(matrix.to_value(), value);
+
+    let point =
+        ContigBox::<f64, NaSVector<f64, 3>>::from_value(&nalgebra::Vector3::new(1.0, 2.0, 3.0));
+    assert_eq!(point.to_value().y, 2.0);
+}
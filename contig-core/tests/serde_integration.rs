@@ -0,0 +1,255 @@
+#![cfg(feature = "serde")]
+
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute { angle: f64 },
+    Chain { links: Dyn<[f64]> },
+}
+
+#[contig(scalar = f64)]
+enum Tool {
+    Gripper { finger: Link, force: Opt<f64> },
+    Welder { path: Dyn<[[f64; 2]]> },
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    joint: Joint,
+    imu_bias: Opt<[f64; 3]>,
+    #[contig(fixed_len = 2)]
+    gains: Dyn<[f64]>,
+}
+
+#[contig(scalar = F)]
+struct Pair<F: Copy + 'static + Contig<F, Config = (), Layout = contig_core::ScalarLayout>> {
+    a: F,
+    b: Dyn<[F]>,
+}
+
+#[test]
+fn robot_config_loads_from_json() {
+    let json = r#"{
+        "links": { "len": 2, "elem": { "mass": null, "pos": null } },
+        "joint": { "Chain": { "links": { "len": 3, "elem": null } } },
+        "imu_bias": "Absent",
+        "gains": null
+    }"#;
+    let cfg: RobotCfg = serde_json::from_str(json).unwrap();
+    assert_eq!(cfg.links.len, 2);
+    assert_eq!(
+        cfg.joint,
+        JointCfg::Chain {
+            links: DynArrayConfig { len: 3, elem: () }
+        }
+    );
+    assert_eq!(cfg.imu_bias, Presence::Absent);
+
+    let text = serde_json::to_string(&cfg).unwrap();
+    assert_eq!(serde_json::from_str::<RobotCfg>(&text).unwrap(), cfg);
+    assert_eq!(RobotLayout::from_config(&cfg).len(), 2 * 4 + 3 + 2);
+}
+
+#[test]
+fn layouts_roundtrip_through_json() {
+    let cfg = RobotCfg {
+        links: DynArrayConfig {
+            len: 1,
+            elem: LinkCfg::default(),
+        },
+        joint: JointCfg::Revolute { angle: () },
+        imu_bias: Presence::Present(()),
+        gains: (),
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let text = serde_json::to_string(&layout).unwrap();
+    assert_eq!(serde_json::from_str::<RobotLayout>(&text).unwrap(), layout);
+
+    let cfg = PairCfg::<f32> {
+        a: (),
+        b: DynArrayConfig { len: 4, elem: () },
+    };
+    let text = serde_json::to_string(&cfg).unwrap();
+    assert_eq!(text, r#"{"a":null,"b":{"len":4,"elem":null}}"#);
+    assert_eq!(serde_json::from_str::<PairCfg<f32>>(&text).unwrap(), cfg);
+}
+
+#[test]
+fn present_unit_configs_keep_their_presence() {
+    let cfg = ToolCfg::Gripper {
+        finger: LinkCfg::default(),
+        force: Presence::Present(()),
+    };
+    let text = serde_json::to_string(&cfg).unwrap();
+    assert_eq!(
+        text,
+        r#"{"Gripper":{"finger":{"mass":null,"pos":null},"force":{"Present":null}}}"#
+    );
+    assert_eq!(serde_json::from_str::<ToolCfg>(&text).unwrap(), cfg);
+
+    let layout = ToolLayout::from_config(&cfg);
+    let text = serde_json::to_string(&layout).unwrap();
+    let back = serde_json::from_str::<ToolLayout>(&text).unwrap();
+    assert_eq!(back, layout);
+    assert_eq!(back.len(), 5);
+
+    let bias = Opt::<f64>::layout(&Presence::Present(()));
+    let text = serde_json::to_string(&bias).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Presence<contig_core::ScalarLayout>>(&text).unwrap(),
+        bias
+    );
+}
+
+#[test]
+fn views_serialize_with_field_names() {
+    let cfg = RobotCfg {
+        links: DynArrayConfig {
+            len: 1,
+            elem: LinkCfg::default(),
+        },
+        joint: JointCfg::Revolute { angle: () },
+        imu_bias: Presence::Absent,
+        gains: (),
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let buffer = [10.0, 1.0, 2.0, 3.0, 0.5, 4.0, 5.0];
+    let json = r#"{"links":[{"mass":10.0,"pos":[1.0,2.0,3.0]}],"joint":{"Revolute":{"angle":0.5}},"imu_bias":null,"gains":[4.0,5.0]}"#;
+    assert_eq!(serde_json::to_string(&layout.cview(&buffer)).unwrap(), json);
+
+    let mut copy = [0.0; 7];
+    let mut de = serde_json::Deserializer::from_str(json);
+    layout.view(&mut copy).deserialize_from(&mut de).unwrap();
+    assert_eq!(copy, buffer);
+
+    let view = layout.cview(&buffer);
+    assert_eq!(
+        serde_json::to_string(&view.links()).unwrap(),
+        r#"[{"mass":10.0,"pos":[1.0,2.0,3.0]}]"#
+    );
+    assert_eq!(
+        serde_json::to_string(&view.joint()).unwrap(),
+        r#"{"Revolute":{"angle":0.5}}"#
+    );
+}
+
+#[test]
+fn enum_views_roundtrip_through_json() {
+    let layout = ToolLayout::from_config(&ToolCfg::Gripper {
+        finger: LinkCfg::default(),
+        force: Presence::Present(()),
+    });
+    let buffer = [10.0, 1.0, 2.0, 3.0, 0.5];
+    let json = r#"{"Gripper":{"finger":{"mass":10.0,"pos":[1.0,2.0,3.0]},"force":0.5}}"#;
+    assert_eq!(serde_json::to_string(&layout.cview(&buffer)).unwrap(), json);
+
+    let mut copy = [0.0; 5];
+    let mut de = serde_json::Deserializer::from_str(json);
+    layout.view(&mut copy).deserialize_from(&mut de).unwrap();
+    assert_eq!(copy, buffer);
+
+    let layout = ToolLayout::from_config(&ToolCfg::Welder {
+        path: DynArrayConfig { len: 2, elem: () },
+    });
+    let mut buffer = [0.0; 4];
+    let mut read = |json: &str| {
+        let mut de = serde_json::Deserializer::from_str(json);
+        layout
+            .view(&mut buffer)
+            .deserialize_from(&mut de)
+            .map_err(|e| e.to_string())
+    };
+    assert_eq!(read(r#"{"Welder":{"path":[[1.0,2.0],[3.0,4.0]]}}"#), Ok(()));
+    assert!(
+        read(r#"{"Gripper":{"force":null}}"#)
+            .unwrap_err()
+            .contains("does not match layout variant `Welder`")
+    );
+    assert!(
+        read(r#"{"Welder":{}}"#)
+            .unwrap_err()
+            .contains("missing field `path`")
+    );
+    assert_eq!(buffer, [1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn deserializing_rejects_mismatched_shapes() {
+    let cfg = RobotCfg {
+        links: DynArrayConfig {
+            len: 1,
+            elem: LinkCfg::default(),
+        },
+        joint: JointCfg::Revolute { angle: () },
+        imu_bias: Presence::Present(()),
+        gains: (),
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let mut buffer = vec![0.0; layout.len()];
+    let mut read = |json: &str| {
+        let mut de = serde_json::Deserializer::from_str(json);
+        layout
+            .view(&mut buffer)
+            .deserialize_from(&mut de)
+            .map_err(|e| e.to_string())
+    };
+
+    let ok = r#"{"links":[{"mass":1.0,"pos":[0.0,0.0,0.0]}],"joint":{"Revolute":{"angle":0.5}},"imu_bias":[0.1,0.2,0.3],"gains":[4.0,5.0]}"#;
+    assert_eq!(read(ok), Ok(()));
+    let two_links = ok.replace(
+        r#"[{"mass":1.0,"pos":[0.0,0.0,0.0]}]"#,
+        r#"[{"mass":1.0,"pos":[0.0,0.0,0.0]},{"mass":1.0,"pos":[0.0,0.0,0.0]}]"#,
+    );
+    assert!(
+        read(&two_links)
+            .unwrap_err()
+            .contains("expected 1 elements")
+    );
+    let wrong_variant = ok.replace(r#"{"Revolute":{"angle":0.5}}"#, r#"{"Chain":{"links":[]}}"#);
+    assert!(
+        read(&wrong_variant)
+            .unwrap_err()
+            .contains("does not match layout variant `Revolute`")
+    );
+    let no_bias = ok.replace(r#","imu_bias":[0.1,0.2,0.3]"#, "");
+    assert!(
+        read(&no_bias)
+            .unwrap_err()
+            .contains("missing field `imu_bias`")
+    );
+    let extra = ok.replace(r#""gains""#, r#""extra":1.0,"gains""#);
+    assert!(read(&extra).unwrap_err().contains("unknown field `extra`"));
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn matrices_serialize_as_rows() {
+    use contig_core::SchemaSeed;
+    use contig_core::na_types::{DynMatrixConfig, NaDMatrix};
+
+    type Mats = Dyn<[NaDMatrix<f64>]>;
+    let cfg = DynArrayConfig {
+        len: 1,
+        elem: DynMatrixConfig { rows: 2, cols: 3 },
+    };
+    let layout = Mats::layout(&cfg);
+    let buffer = [1.0, 4.0, 2.0, 5.0, 3.0, 6.0];
+    let json = serde_json::to_string(&Mats::view(&layout, &buffer)).unwrap();
+    assert_eq!(json, "[[[1.0,2.0,3.0],[4.0,5.0,6.0]]]");
+
+    let mut copy = [0.0; 6];
+    let schema = Mats::schema(&layout, 0);
+    let mut de = serde_json::Deserializer::from_str(&json);
+    serde::de::DeserializeSeed::deserialize(SchemaSeed::new(&schema, &mut copy), &mut de).unwrap();
+    assert_eq!(copy, buffer);
+}
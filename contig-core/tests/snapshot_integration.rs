@@ -0,0 +1,239 @@
+#![cfg(feature = "snapshot")]
+
+use contig_core::prelude::*;
+use contig_core::snapshot::{self, SnapshotError};
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    imu_bias: Opt<[f64; 3]>,
+}
+
+#[contig(scalar = f64)]
+struct State {
+    x: f64,
+    bias: Opt<f64>,
+    offset: Opt<[f64; 3]>,
+}
+
+mod reordered {
+    use contig_derive::contig;
+
+    // Same fields as `super::Link`, declared in a different order.
+    #[contig(scalar = f64)]
+    pub struct Link {
+        pub pos: [f64; 3],
+        pub mass: f64,
+    }
+}
+
+fn robot() -> (RobotCfg, ContigBox<f64, Robot>) {
+    let cfg = RobotCfg {
+        links: DynArrayConfig {
+            len: 2,
+            elem: LinkCfg::default(),
+        },
+        imu_bias: Presence::Present(()),
+    };
+    let mut robot = ContigBox::<f64, Robot>::new(&cfg);
+    for (i, x) in robot.iter_mut().enumerate() {
+        *x = i as f64 * 0.5;
+    }
+    (cfg, robot)
+}
+
+/// Re-encode `file` with its JSON header replaced by `edit(header)`.
+fn with_header(file: &[u8], edit: impl FnOnce(&str) -> String) -> Vec<u8> {
+    let header_len = u64::from_le_bytes(file[12..20].try_into().unwrap()) as usize;
+    let header = edit(std::str::from_utf8(&file[20..20 + header_len]).unwrap());
+    let mut forged = file[..12].to_vec();
+    forged.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    forged.extend_from_slice(header.as_bytes());
+    forged.extend_from_slice(&file[20 + header_len..]);
+    forged
+}
+
+#[test]
+fn snapshots_roundtrip_layout_and_buffer() {
+    let (cfg, robot) = robot();
+    let mut file = Vec::new();
+    robot.save(&cfg, &mut file).unwrap();
+    assert_eq!(&file[..8], b"CONTIGSN");
+
+    let loaded = ContigBox::<f64, Robot>::load(file.as_slice()).unwrap();
+    assert_eq!(loaded.layout(), robot.layout());
+    assert_eq!(loaded.as_slice(), robot.as_slice());
+
+    let (layout, buf) = snapshot::load::<f64, Robot, _>(file.as_slice()).unwrap();
+    assert_eq!(layout.cview(&buf).links().len(), 2);
+
+    let other = RobotCfg {
+        imu_bias: Presence::Absent,
+        ..cfg
+    };
+    let err = robot.save(&other, &mut Vec::new()).unwrap_err();
+    assert!(matches!(err, SnapshotError::Contig(_)), "{}", err);
+}
+
+#[test]
+fn present_optional_fields_roundtrip() {
+    let cfg = StateCfg {
+        x: (),
+        bias: Presence::Present(()),
+        offset: Presence::Present(()),
+    };
+    let mut state = ContigBox::<f64, State>::new(&cfg);
+    state.copy_from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    let mut file = Vec::new();
+    state.save(&cfg, &mut file).unwrap();
+
+    let loaded = ContigBox::<f64, State>::load(file.as_slice()).unwrap();
+    assert_eq!(loaded.layout(), state.layout());
+    assert_eq!(loaded.as_slice(), state.as_slice());
+    assert_eq!(loaded.view().bias().copied(), Some(2.0));
+
+    let absent = StateCfg {
+        bias: Presence::Absent,
+        ..cfg
+    };
+    let mut file = Vec::new();
+    snapshot::save::<f64, State, _>(&absent, &[1.0, 3.0, 4.0, 5.0], &mut file).unwrap();
+    let (layout, buf) = snapshot::load::<f64, State, _>(file.as_slice()).unwrap();
+    assert!(layout.cview(&buf).bias().is_none());
+    assert_eq!(buf, [1.0, 3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn loading_rejects_changed_types() {
+    let mut file = Vec::new();
+    snapshot::save::<f64, Link, _>(&LinkCfg::default(), &[1.0, 2.0, 3.0, 4.0], &mut file).unwrap();
+
+    let err = snapshot::load::<f64, reordered::Link, _>(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(err, SnapshotError::SchemaMismatch { .. }),
+        "{}",
+        err
+    );
+    let err = snapshot::load::<f32, [f32; 4], _>(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            SnapshotError::ScalarMismatch {
+                expected: "f32",
+                ..
+            }
+        ),
+        "{}",
+        err
+    );
+    let err = snapshot::load::<f64, Robot, _>(file.as_slice()).unwrap_err();
+    assert!(matches!(err, SnapshotError::Layout(_)), "{}", err);
+    let err = snapshot::load::<f64, [f64; 3], _>(file.as_slice()).unwrap_err();
+    assert!(matches!(err, SnapshotError::Layout(_)), "{}", err);
+
+    let err = snapshot::load::<f64, Link, _>(&file[..file.len() - 1]).unwrap_err();
+    assert!(matches!(err, SnapshotError::Io(_)), "{}", err);
+    file[0] = b'X';
+    let err = snapshot::load::<f64, Link, _>(file.as_slice()).unwrap_err();
+    assert!(matches!(err, SnapshotError::BadMagic), "{}", err);
+}
+
+#[test]
+fn forged_lengths_fail_without_allocating() {
+    let mut file = Vec::new();
+    snapshot::save::<f64, Link, _>(&LinkCfg::default(), &[1.0, 2.0, 3.0, 4.0], &mut file).unwrap();
+
+    // A header length far beyond the file.
+    let mut forged = file[..12].to_vec();
+    forged.extend_from_slice(&(1u64 << 46).to_le_bytes());
+    let err = snapshot::load::<f64, Link, _>(forged.as_slice()).unwrap_err();
+    assert!(matches!(err, SnapshotError::Io(_)), "{}", err);
+
+    // A scalar count (and matching Dyn length) far beyond the payload.
+    let mut forged = Vec::new();
+    let cfg = DynArrayConfig { len: 1, elem: () };
+    snapshot::save::<f64, Dyn<[f64]>, _>(&cfg, &[0.0], &mut forged).unwrap();
+    let rewrite = |from: &str, to: &str| {
+        let file = with_header(&forged, |header| header.replace(from, to));
+        snapshot::load::<f64, Dyn<[f64]>, _>(file.as_slice()).unwrap_err()
+    };
+    let huge = format!(r#""len":{},"#, 1u64 << 40);
+    let err = rewrite(r#""len":1,"#, &huge);
+    assert!(matches!(err, SnapshotError::Io(_)), "{}", err);
+
+    // Only the stored layout claims a huge length; the layout rebuilt from the config wins.
+    let err = rewrite(r#""layout":{"len":1,"#, &format!(r#""layout":{{{}"#, huge));
+    assert!(matches!(err, SnapshotError::Layout(_)), "{}", err);
+}
+
+#[test]
+fn forged_layout_offsets_are_refused() {
+    let mut file = Vec::new();
+    snapshot::save::<f64, Link, _>(&LinkCfg::default(), &[1.0, 2.0, 3.0, 4.0], &mut file).unwrap();
+    let forged = with_header(&file, |header| {
+        let pos = r#""off_pos":{"start":1,"end":4}"#;
+        assert!(header.contains(pos), "{}", header);
+        header.replace(pos, r#""off_pos":{"start":1,"end":40}"#)
+    });
+    let err = snapshot::load::<f64, Link, _>(forged.as_slice()).unwrap_err();
+    assert!(matches!(err, SnapshotError::Layout(_)), "{}", err);
+}
+
+mod edited {
+    use contig_core::prelude::*;
+    use contig_derive::contig;
+
+    // `Link` gained a field and `Robot` swapped `imu_bias` for `time`.
+    #[contig(scalar = f64)]
+    pub struct Link {
+        pub inertia: f64,
+        pub mass: f64,
+        pub pos: [f64; 3],
+    }
+
+    #[contig(scalar = f64)]
+    pub struct Robot {
+        #[contig(len)]
+        pub links: Dyn<[Link]>,
+        pub time: f64,
+    }
+}
+
+#[test]
+fn old_snapshots_migrate_into_edited_types() {
+    let (cfg, robot) = robot();
+    let mut file = Vec::new();
+    robot.save(&cfg, &mut file).unwrap();
+
+    let cfg = edited::RobotCfg {
+        links: DynArrayConfig {
+            len: 2,
+            elem: edited::LinkCfg::default(),
+        },
+        time: (),
+    };
+    let (layout, buf, report) =
+        snapshot::load_migrated::<f64, edited::Robot, _>(file.as_slice(), &cfg).unwrap();
+    assert_eq!(report.kept, 8);
+    assert_eq!(report.added, ["links.0.inertia", "links.1.inertia", "time"]);
+    assert_eq!(report.removed, ["imu_bias"]);
+
+    let view = layout.cview(&buf);
+    assert_eq!(view.links().get(1).as_slice(), [0.0, 2.0, 2.5, 3.0, 3.5]);
+    assert_eq!(*view.time(), 0.0);
+
+    let err = snapshot::load_migrated::<f32, [f32; 1], _>(file.as_slice(), &()).unwrap_err();
+    assert!(
+        matches!(err, SnapshotError::ScalarMismatch { .. }),
+        "{}",
+        err
+    );
+}
@@ -9,12 +9,24 @@ pub struct Link {
     pos: Vec3<f64>,
 }
 
+#[contig(scalar = f64)]
+pub enum Joint {
+    Revolute { angle: f64 },
+    Prismatic { offset: f64 },
+    Spherical { quat: [f64; 4] },
+}
+
 #[contig(scalar = f64)]
 pub struct Robot {
     #[contig(len)]
     links: Dyn<[Link]>,
     #[contig(len, elem_shape)]
     mats: Dyn<[na::NaDMatrix<f64>]>,
+    #[contig(len)]
+    joints: Ragged<[Joint]>,
+    #[contig(len)]
+    collision_spheres: Ragged<[Dyn<[[f64; 4]]>]>,
+    imu_bias: Opt<Vec3<f64>>,
 }
 
 fn main() {
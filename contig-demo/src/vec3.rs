@@ -1,4 +1,4 @@
-use contig_core::Contig;
+use contig_core::{Contig, ContigStatic, SchemaKind, SchemaNode, Segment};
 use core::marker::PhantomData;
 
 /// Marker type representing a fixed `[F; 3]` contiguous vector.
@@ -80,6 +80,18 @@ impl<F> Contig<F> for Vec3<F> {
         3
     }
 
+    fn schema(_layout: &Self::Layout, offset: usize) -> SchemaNode {
+        let children = ["x", "y", "z"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                SchemaNode::new(SchemaKind::Scalar, offset + i..offset + i + 1)
+                    .with_segment(Segment::Field(name))
+            })
+            .collect();
+        SchemaNode::new(SchemaKind::Struct, offset..offset + 3).with_children(children)
+    }
+
     fn view<'a>(_layout: &'a Self::Layout, buf: &'a [F]) -> Self::ConstView<'a> {
         debug_assert!(buf.len() >= 3);
         Vec3View { slice: &buf[..3] }
@@ -92,3 +104,22 @@ impl<F> Contig<F> for Vec3<F> {
         }
     }
 }
+
+impl<F> ContigStatic<F> for Vec3<F> {
+    const LEN: usize = 3;
+    const LAYOUT: Self::Layout = Vec3Layout;
+
+    fn view_static<'a>(buf: &'a [F]) -> Self::ConstView<'a>
+    where
+        F: 'a,
+    {
+        Self::view(&Vec3Layout, buf)
+    }
+
+    fn view_static_mut<'a>(buf: &'a mut [F]) -> Self::MutView<'a>
+    where
+        F: 'a,
+    {
+        Self::view_mut(&Vec3Layout, buf)
+    }
+}
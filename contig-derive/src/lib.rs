@@ -1,15 +1,26 @@
 //! `#[contig]` derive: generates config/layout/view types plus a `Contig` impl
-//! for concrete user structs.
+//! for user structs.
 //!
-//! The macro requires `#[contig(scalar = <ty>)]` to specify the scalar type (e.g. `f64`).
-//! It only supports non-generic structs with named fields; per-field `#[contig(...)]`
-//! attributes determine whether a field is dynamic and what runtime arguments it needs.
+//! The macro requires `#[contig(scalar = <ty>)]` to specify the scalar type (e.g. `f64`);
+//! the scalar may also be one of the struct's own type parameters (`#[contig(scalar = F)]`).
+//! It supports named, tuple, and unit structs, and enums whose variants carry named fields
+//! (tagged unions sized to the variant chosen in the config), generic over types and
+//! constants (but not lifetimes); per-field `#[contig(...)]` attributes (`skip`, `rename`,
+//! `default`, `fixed_len`, and the `len`/`elem_shape` markers) adjust how each field is
+//! configured and exposed. `#[contig(scalar = <ty>, owned)]` additionally emits a plain
+//! `Value` type that packs into and unpacks from the buffer.
+//!
+//! When the linked `contig-core` has its `serde` feature enabled, the generated configs and
+//! layouts implement `Serialize` and `Deserialize` whenever their field configs and layouts
+//! do, so a `RobotCfg` can be read straight from a JSON or TOML description. Views then
+//! implement `Serialize` with their field names (`{"links":[{"mass":10.0,...}],...}`), and
+//! views read the same form back with `view.deserialize_from(deserializer)`.
 
 use proc_macro::TokenStream;
 use quote::{ToTokens, format_ident, quote};
 use syn::{
-    Attribute, Data, DeriveInput, Fields, MetaNameValue, Token, Type, parse::Parser,
-    parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned,
+    Attribute, Data, DeriveInput, Fields, Generics, Meta, Token, Type, ext::IdentExt,
+    parse::Parser, parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned,
 };
 
 /// Clone a field, removing any `#[contig(...)]` helper attributes so they are
@@ -20,32 +31,217 @@ fn strip_contig_attrs(field: &syn::Field) -> syn::Field {
     clone
 }
 
-/// Parse the scalar type from the attribute arguments (`#[contig(scalar = ...)]`).
-fn parse_scalar_type(attr: TokenStream) -> syn::Result<Type> {
-    let parser = Punctuated::<MetaNameValue, Token![,]>::parse_terminated;
+/// Options given on the item itself: `#[contig(scalar = f64, owned)]`.
+struct ItemArgs {
+    /// `scalar = ty`: element type of the backing buffer.
+    scalar_ty: Type,
+    /// `owned`: also emit an owned `Value` type and a `ContigValue` impl.
+    owned: bool,
+}
+
+/// Parse the attribute arguments (`#[contig(scalar = ...)]`, optionally followed by `owned`).
+/// Unknown or repeated arguments are rejected at the offending argument.
+fn parse_item_args(attr: TokenStream) -> syn::Result<ItemArgs> {
+    let parser = Punctuated::<Meta, Token![,]>::parse_terminated;
     let args = parser
         .parse2(attr.into())
         .map_err(|e| syn::Error::new(e.span(), "invalid #[contig] arguments"))?;
 
-    for nv in args {
-        if nv.path.is_ident("scalar") {
-            let ty_tokens = nv.value.to_token_stream();
-            return syn::parse2::<Type>(ty_tokens).map_err(|err| {
-                syn::Error::new(err.span(), "scalar must be a type path (e.g., f64)")
-            });
+    let mut scalar_ty = None;
+    let mut owned = false;
+    for arg in args {
+        match arg {
+            Meta::NameValue(nv) if nv.path.is_ident("scalar") => {
+                if scalar_ty.is_some() {
+                    return Err(syn::Error::new(
+                        nv.path.span(),
+                        "duplicate #[contig] argument `scalar`",
+                    ));
+                }
+                let ty_tokens = nv.value.to_token_stream();
+                scalar_ty = Some(syn::parse2::<Type>(ty_tokens).map_err(|err| {
+                    syn::Error::new(err.span(), "scalar must be a type path (e.g., f64)")
+                })?);
+            }
+            Meta::Path(path) if path.is_ident("owned") => {
+                if owned {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        "duplicate #[contig] argument `owned`",
+                    ));
+                }
+                owned = true;
+            }
+            other => {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "invalid #[contig] arguments; expected `scalar = <ty>` and optionally `owned`",
+                ));
+            }
         }
     }
 
-    Err(syn::Error::new_spanned(
-        quote! { #[contig(scalar = <ty>)] },
-        "missing `scalar` attribute: use #[contig(scalar = f64)]",
-    ))
+    let scalar_ty = scalar_ty.ok_or_else(|| {
+        syn::Error::new_spanned(
+            quote! { #[contig(scalar = <ty>)] },
+            "missing `scalar` attribute: use #[contig(scalar = f64)]",
+        )
+    })?;
+    Ok(ItemArgs { scalar_ty, owned })
+}
+
+/// Options collected from a field's `#[contig(...)]` helper attributes.
+#[derive(Default)]
+struct FieldFlags {
+    /// `skip`: the field takes no part in the config, layout, or views.
+    skip: Option<proc_macro2::Span>,
+    /// `rename = ident`: name used for the accessor and generated members.
+    rename: Option<syn::Ident>,
+    /// `default`: the field is left out of the `Cfg` and configured with `Default`.
+    default: Option<proc_macro2::Span>,
+    /// `fixed_len = N`: a `Dyn<[T]>` field with a compile-time length; its `Cfg` entry
+    /// configures a single element.
+    fixed_len: Option<syn::Expr>,
+    /// `len`: marks a `Dyn<[T]>` or `Ragged<[T]>` field whose length comes from the `Cfg`.
+    len: Option<proc_macro2::Span>,
+    /// `elem_shape`: marks an array field whose elements are sized by the `Cfg` as well.
+    elem_shape: Option<proc_macro2::Span>,
+}
+
+/// Every key accepted inside a field-level `#[contig(...)]`.
+const FIELD_KEYS: &[&str] = &[
+    "skip",
+    "rename",
+    "default",
+    "fixed_len",
+    "len",
+    "elem_shape",
+];
+
+/// Pairs of keys that cannot be combined on one field.
+const FIELD_KEY_CONFLICTS: &[(&str, &str)] = &[
+    ("skip", "rename"),
+    ("skip", "default"),
+    ("skip", "fixed_len"),
+    ("skip", "len"),
+    ("skip", "elem_shape"),
+    ("fixed_len", "len"),
+    ("fixed_len", "default"),
+];
+
+/// Parse field-level helper attributes.
+///
+/// `len` and `elem_shape` are markers documenting that an array field takes its length
+/// (and element shape) from the config, which is already the default, and are checked
+/// against the field type by [`check_markers`]; the remaining keys change what is
+/// generated. Unknown, repeated, or conflicting keys are rejected at the offending key.
+fn parse_flags(attrs: &[Attribute]) -> syn::Result<FieldFlags> {
+    let mut flags = FieldFlags::default();
+    let mut seen: Vec<&'static str> = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("contig")) {
+        attr.parse_nested_meta(|meta| {
+            let Some(key) = FIELD_KEYS
+                .iter()
+                .copied()
+                .find(|key| meta.path.is_ident(key))
+            else {
+                let name = meta.path.to_token_stream().to_string().replace(' ', "");
+                return Err(meta.error(format!(
+                    "unknown #[contig] field attribute `{}`; expected one of `skip`, `rename`, \
+                     `default`, `fixed_len`, `len`, `elem_shape`",
+                    name
+                )));
+            };
+            if seen.contains(&key) {
+                return Err(meta.error(format!("duplicate #[contig] field attribute `{}`", key)));
+            }
+            if let Some(other) = FIELD_KEY_CONFLICTS.iter().find_map(|&(a, b)| {
+                match (a == key && seen.contains(&b), b == key && seen.contains(&a)) {
+                    (true, _) => Some(b),
+                    (_, true) => Some(a),
+                    _ => None,
+                }
+            }) {
+                return Err(meta.error(format!("`{}` cannot be combined with `{}`", key, other)));
+            }
+            seen.push(key);
+
+            let takes_value = matches!(key, "rename" | "fixed_len");
+            if !takes_value && !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error(format!("`{}` does not take a value", key)));
+            }
+            match key {
+                "skip" => flags.skip = Some(meta.path.span()),
+                "default" => flags.default = Some(meta.path.span()),
+                "rename" => flags.rename = Some(meta.value()?.parse()?),
+                "fixed_len" => flags.fixed_len = Some(meta.value()?.parse()?),
+                "len" => flags.len = Some(meta.path.span()),
+                "elem_shape" => flags.elem_shape = Some(meta.path.span()),
+                _ => unreachable!("every field key is handled"),
+            }
+            Ok(())
+        })?;
+    }
+    Ok(flags)
+}
+
+/// Element type `T` of a field written as `Dyn<[T]>`, as required by `fixed_len`.
+fn dyn_elem_type(ty: &Type) -> Option<&Type> {
+    array_elem_type(ty, "Dyn")
+}
+
+/// Element type `T` of a field written as `<adapter><[T]>`.
+fn array_elem_type<'t>(ty: &'t Type, adapter: &str) -> Option<&'t Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != adapter {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(Type::Slice(slice)) if args.args.len() == 1 => Some(&slice.elem),
+        _ => None,
+    }
 }
 
-/// Field-level helper attributes are currently parsed but unused; this stub
-/// remains so we can extend handling later without touching call sites.
-fn parse_flags(attrs: &[Attribute]) {
-    let _ = attrs;
+/// Reject the `len` and `elem_shape` markers on fields they cannot describe: both need a
+/// `Dyn<[T]>` or `Ragged<[T]>` field, and `elem_shape` on a `Dyn` also needs elements whose
+/// size is not visibly fixed (the scalar itself, or fixed-size arrays of it).
+fn check_markers(flags: &FieldFlags, ty: &Type, scalar_ty: &Type) -> syn::Result<()> {
+    fn is_fixed(ty: &Type, scalar_ty: &Type) -> bool {
+        match ty {
+            Type::Array(array) => is_fixed(&array.elem, scalar_ty),
+            Type::Paren(paren) => is_fixed(&paren.elem, scalar_ty),
+            ty => ty == scalar_ty,
+        }
+    }
+
+    let dyn_elem = dyn_elem_type(ty);
+    let is_array = dyn_elem.is_some() || array_elem_type(ty, "Ragged").is_some();
+    for (span, key) in [(flags.len, "len"), (flags.elem_shape, "elem_shape")] {
+        if let Some(span) = span.filter(|_| !is_array) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`{}` requires a field of type `Dyn<[T]>` or `Ragged<[T]>`",
+                    key
+                ),
+            ));
+        }
+    }
+    match (flags.elem_shape, dyn_elem) {
+        (Some(span), Some(elem)) if is_fixed(elem, scalar_ty) => Err(syn::Error::new(
+            span,
+            format!(
+                "`elem_shape` requires elements sized at runtime, but every `{}` has the same \
+                 size",
+                elem.to_token_stream().to_string().replace(' ', "")
+            ),
+        )),
+        _ => Ok(()),
+    }
 }
 
 /// Expand a struct annotated with `#[contig(...)]` into a fully operational
@@ -75,50 +271,350 @@ fn parse_flags(attrs: &[Attribute]) {
 ///
 /// The macro preserves the user-written struct (minus helper attributes) and
 /// emits sibling `Cfg`, `Layout`, `View`, and `ConstView` types alongside a
-/// [`contig_core::Contig`] implementation.
+/// [`contig_core::Contig`] implementation. Generic parameters and where-clauses are
+/// carried onto every generated type, so one definition serves several scalars:
+///
+/// ```
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = F)]
+/// struct Body<F: Copy + 'static> {
+///     mass: F,
+///     inertia: [F; 3],
+/// }
+///
+/// let layout = BodyLayout::<f32>::from_config(&BodyCfg { mass: (), inertia: () });
+/// let mut buffer = vec![0.0f32; layout.len()];
+/// *layout.view(&mut buffer).mass() = 2.0;
+/// assert_eq!(buffer[0], 2.0);
+/// ```
+///
+/// Besides the panicking `from_config`/`view`/`cview`, layouts offer `try_from_config`,
+/// `try_view`, and `try_cview`, which return [`contig_core::ContigError`] on overflow or
+/// short buffers. `schema()` reflects a layout as a [`contig_core::SchemaNode`] tree whose
+/// children are the fields, named after their accessors, and `path_of(index)` names the
+/// field holding a flat buffer index (e.g. `links[3].pos.y`). Conversely, `range_of(path)`,
+/// `get_path`, and `get_path_mut` resolve such a path to its scalars. `flat_names()` lists
+/// one dotted name per scalar (`links.0.pos.x`) for headers and legends, and
+/// `schema_hash()` fingerprints the whole shape and the scalar type so buffers from a
+/// differently shaped producer can be rejected. When a config
+/// changes, `layout.relayout(&buffer, &new_cfg)` builds the new layout and buffer, copying
+/// every field the two have in common and defaulting new slots; after the struct itself
+/// was edited, `contig_core::migrate` matches old and new fields by their `flat_names()`.
+///
+/// When the scalar implements [`contig_core::Real`], struct views also offer whole-value
+/// arithmetic over exactly the scalars they cover: `fill`, `copy_from`, `axpy`, and `scale`
+/// on mutable views, and `dot` and `norm` on both kinds. Array fields offer the same
+/// methods, so an update can be restricted to one field
+/// (`view.links().axpy(alpha, &dx.links())`). Enum views do not get these methods: they
+/// hold only the active variant's field views, not the range those share, so apply the
+/// kernels in [`contig_core::ops`] to `&mut buffer[..layout.len()]` instead.
+///
+/// Configs and layouts implement `Debug` and `PartialEq`, and views print as the user type
+/// with its field names, arrays as lists: `Robot { links: [Link { mass: 10.0, .. }], .. }`.
+/// The alternate form (`{:#?}`) spreads the same tree over several lines.
+///
+/// Accessors borrow the whole view, so `View::fields_mut()` hands out every field at once
+/// as disjoint mutable views in a generated `FieldsMut` struct:
+///
+/// ```
+/// use contig_core::prelude::*;
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64)]
+/// struct State {
+///     time: f64,
+///     #[contig(len)]
+///     rates: Dyn<[f64]>,
+/// }
+///
+/// let cfg = StateCfg { time: (), rates: DynArrayConfig { len: 2, elem: () } };
+/// let layout = StateLayout::from_config(&cfg);
+/// let mut buffer = vec![0.0; layout.len()];
+/// let mut view = layout.view(&mut buffer);
+/// let StateFieldsMut { time, mut rates } = view.fields_mut();
+/// *rates.get_mut(1) = 3.0;
+/// *time = *rates.get_mut(1) * 0.5;
+/// assert_eq!(buffer, [1.5, 0.0, 3.0]);
+/// ```
+///
+/// It also emits a [`contig_core::ContigStatic`] implementation that only holds when
+/// every field is static, so all-static structs get a compile-time `LEN` and can be
+/// viewed without a runtime layout:
+///
+/// ```
+/// use contig_core::prelude::*;
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64)]
+/// struct Joint {
+///     angle: f64,
+///     axis: [f64; 3],
+/// }
+///
+/// let mut buffer = [0.0; Joint::LEN];
+/// *Joint::view_static_mut(&mut buffer).angle() = 0.25;
+/// assert_eq!(*Joint::view_static(&buffer).angle(), 0.25);
+/// ```
+///
+/// Enums become tagged unions: the generated `Cfg` is an enum selecting the variant, the
+/// footprint matches that variant, and views are enums of per-variant field views:
+///
+/// ```
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64)]
+/// enum Joint {
+///     Revolute { angle: f64 },
+///     Prismatic { offset: f64, axis: [f64; 3] },
+/// }
+///
+/// let layout = JointLayout::from_config(&JointCfg::Revolute { angle: () });
+/// let mut buffer = vec![0.0; layout.len()];
+/// if let JointView::Revolute { angle } = layout.view(&mut buffer) {
+///     *angle = 1.5;
+/// }
+/// assert_eq!(buffer, [1.5]);
+/// ```
+///
+/// A `Dyn<[Joint]>` shares one element config, so all of its joints are the same variant;
+/// collections that mix variants (revolute and prismatic joints in one robot) need
+/// `Ragged<[Joint]>`, which configures each element on its own.
+///
+/// Tuple structs get positional `_0()`, `_1()`, ... accessors (or the name given with
+/// `#[contig(rename = ident)]`), and unit structs act as zero-length placeholders:
+///
+/// ```
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64)]
+/// struct Span(#[contig(rename = start)] f64, f64);
+///
+/// #[contig(scalar = f64)]
+/// struct Marker;
+///
+/// let layout = SpanLayout::from_config(&SpanCfg((), ()));
+/// let mut buffer = vec![0.0; layout.len()];
+/// *layout.view(&mut buffer)._1() = 4.0;
+/// assert_eq!(*layout.cview(&buffer).start(), 0.0);
+/// assert!(MarkerLayout::from_config(&MarkerCfg).is_empty());
+/// ```
+///
+/// With `owned`, the macro also emits a plain `Value` type mirroring the fields (`Vec` for
+/// `Dyn`, `Option` for `Opt`, nested `Value`s for `#[contig(owned)]` fields) and implements
+/// [`contig_core::ContigValue`]. `config_of` infers the config, including `Dyn` lengths
+/// (elements of a `Dyn` must share one config; use `Ragged` otherwise), and struct values
+/// `pack` into a view and `unpack` from one:
+///
+/// ```
+/// use contig_core::prelude::*;
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64, owned)]
+/// struct Link {
+///     mass: f64,
+///     pos: [f64; 3],
+/// }
+///
+/// #[contig(scalar = f64, owned)]
+/// struct Robot {
+///     #[contig(len)]
+///     links: Dyn<[Link]>,
+/// }
+///
+/// let value = RobotValue {
+///     links: vec![LinkValue { mass: 2.0, pos: [0.0, 1.0, 0.0] }],
+/// };
+/// let layout = RobotLayout::from_config(&value.config_of());
+/// let mut buffer = vec![0.0; layout.len()];
+/// value.pack(&mut layout.view(&mut buffer));
+/// assert_eq!(buffer, [2.0, 0.0, 1.0, 0.0]);
+/// assert_eq!(RobotValue::unpack(&layout.cview(&buffer)), value);
+/// ```
+///
+/// Fields accept `#[contig(...)]` helper attributes; unknown or conflicting keys are errors:
+///
+/// - `skip`: the field is ignored entirely and need not implement `Contig`.
+/// - `rename = ident`: names the accessor (and `Cfg` field) `ident`.
+/// - `default`: the field is left out of `Cfg` and configured with `Default::default()`.
+/// - `fixed_len = N`: on a `Dyn<[T]>` field, fixes the length to `N`; `Cfg` holds the
+///   element config.
+/// - `len`, `elem_shape`: markers for `Dyn<[T]>` and `Ragged<[T]>` fields whose length
+///   (and element shape) come from `Cfg`; `elem_shape` is rejected on a `Dyn` of scalars or
+///   fixed-size arrays of them, whose elements always have the same size.
+///
+/// ```
+/// use contig_core::prelude::*;
+/// use contig_derive::contig;
+///
+/// #[contig(scalar = f64)]
+/// struct Filter {
+///     #[contig(skip)]
+///     name: String,
+///     #[contig(fixed_len = 4)]
+///     taps: Dyn<[f64]>,
+///     #[contig(default)]
+///     gain: f64,
+/// }
+///
+/// let layout = FilterLayout::from_config(&FilterCfg { taps: () });
+/// assert_eq!(layout.len(), 5);
+/// ```
 #[proc_macro_attribute]
 pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let scalar_ty = match parse_scalar_type(attr) {
-        Ok(ty) => ty,
+    let args = match parse_item_args(attr) {
+        Ok(args) => args,
         Err(err) => return err.to_compile_error().into(),
     };
 
     let input = parse_macro_input!(item as DeriveInput);
 
-    if !input.generics.params.is_empty() {
+    if let Some(lifetime) = input.generics.lifetimes().next() {
         return syn::Error::new(
-            input.generics.span(),
-            "#[contig] does not support generic structs; instantiate a concrete type",
+            lifetime.span(),
+            "#[contig] does not support lifetime parameters; views already borrow the buffer",
         )
         .to_compile_error()
         .into();
     }
 
-    let struct_ident = input.ident.clone();
-    let data = match &input.data {
-        Data::Struct(ds) => ds,
-        _ => {
-            return syn::Error::new(input.span(), "#[contig] supports only structs")
-                .to_compile_error()
-                .into();
-        }
-    };
-    let fields = match &data.fields {
-        Fields::Named(named) => &named.named,
-        _ => {
-            return syn::Error::new(input.span(), "#[contig] requires named fields")
-                .to_compile_error()
-                .into();
-        }
+    let expanded = match &input.data {
+        Data::Struct(data) => expand_struct(&args, &input, &data.fields),
+        Data::Enum(data) => expand_enum(&args, &input, data),
+        Data::Union(_) => Err(syn::Error::new(
+            input.span(),
+            "#[contig] supports only structs and enums",
+        )),
     };
 
-    let vis = input.vis.clone();
-    let retained_attrs: Vec<Attribute> = input
+    expanded.unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Retain the user's non-`contig` attributes for re-emission.
+fn retained_attrs(input: &DeriveInput) -> Vec<Attribute> {
+    input
         .attrs
         .iter()
         .filter(|attr| !attr.path().is_ident("contig"))
         .cloned()
-        .collect();
+        .collect()
+}
+
+/// Build the generics shared by every generated item: the user's parameters and
+/// where-clause plus `'static` on type parameters (as with `Dyn<[T]>` elements, so the
+/// layout they appear in outlives any view lifetime) and the field `Contig` bounds.
+fn item_generics(input: &DeriveInput, contig_bounds: &[syn::WherePredicate]) -> Generics {
+    let static_params = input.generics.type_params().map(|param| {
+        let ident = &param.ident;
+        parse_quote! { #ident: 'static }
+    });
+    with_predicates(
+        &input.generics,
+        static_params.chain(contig_bounds.iter().cloned()),
+    )
+}
+
+/// Clone `generics`, appending `predicates` to its where-clause.
+fn with_predicates(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = syn::WherePredicate>,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Bound letting an owned `Value` be cloned, printed, and compared whenever the value of
+/// field type `fty` can; deferred like the static bounds so other impls still compile.
+fn value_field_bound(scalar_ty: &Type, fty: &Type) -> syn::WherePredicate {
+    parse_quote! {
+        for<'__contig> <#fty as contig_core::ContigValue<#scalar_ty>>::Value:
+            core::clone::Clone + core::fmt::Debug + core::cmp::PartialEq
+    }
+}
+
+/// Serde derives for a generated config or layout storing values of the types in `stored`,
+/// bounded on exactly those types; pass them to [`with_serde`] along with the item.
+fn serde_attrs(stored: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    let bounds = |bound: proc_macro2::TokenStream| {
+        stored
+            .iter()
+            .map(|ty| quote! { for<'__contig> #ty: #bound }.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let ser = bounds(quote! { contig_core::serde::Serialize });
+    let de = bounds(quote! { contig_core::serde::Deserialize<'de> });
+    quote! {
+        #[derive(contig_core::serde::Serialize, contig_core::serde::Deserialize)]
+        #[serde(crate = "contig_core::serde", bound(serialize = #ser, deserialize = #de))]
+    }
+}
+
+/// Emit `serde` (derive attributes or whole impls) ahead of `item` only when the user's
+/// `contig-core` has its `serde` feature on; `contig_core::__with_serde!` makes that call.
+fn with_serde(
+    serde: proc_macro2::TokenStream,
+    item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        contig_core::__with_serde! { { #serde } #item }
+    }
+}
+
+/// `Self::V { a, .. } => f.debug_struct("V").field("a", a)...finish()` for an enum `Debug`.
+fn debug_arm(vname: &syn::Ident, members: &[syn::Ident]) -> proc_macro2::TokenStream {
+    let variant = vname.unraw().to_string();
+    let names = members.iter().map(|member| member.unraw().to_string());
+    quote! {
+        Self::#vname { #( #members ),* } => f
+            .debug_struct(#variant)
+            #( .field(#names, #members) )*
+            .finish()
+    }
+}
+
+/// Arm comparing two values of variant `vname` field by field for an enum `PartialEq`.
+fn eq_arm(vname: &syn::Ident, members: &[syn::Ident]) -> proc_macro2::TokenStream {
+    let lhs: Vec<_> = members.iter().map(|m| format_ident!("lhs_{}", m)).collect();
+    let rhs: Vec<_> = members.iter().map(|m| format_ident!("rhs_{}", m)).collect();
+    quote! {
+        (Self::#vname { #( #members: #lhs ),* }, Self::#vname { #( #members: #rhs ),* }) => {
+            true #( && #lhs == #rhs )*
+        }
+    }
+}
+
+/// Prepend the view lifetime `'a` to `generics`.
+fn view_generics(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!('a));
+    generics
+}
+
+/// View generics with a leading `'de` for `DeserializeView<'de>` impls, plus `predicates`.
+fn deserialize_view_generics(
+    view_generics: &Generics,
+    predicates: impl IntoIterator<Item = syn::WherePredicate>,
+) -> Generics {
+    let mut generics = with_predicates(view_generics, predicates);
+    generics.params.insert(0, parse_quote!('de));
+    generics
+}
+
+/// Expand a struct with named fields, positional fields, or none at all.
+///
+/// Tuple-struct fields get `_0()`, `_1()`, ... accessors unless renamed, and their `Cfg`
+/// stays positional; unit structs occupy no scalars.
+fn expand_struct(
+    args: &ItemArgs,
+    input: &DeriveInput,
+    fields: &Fields,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let scalar_ty = &args.scalar_ty;
+    let struct_ident = input.ident.clone();
+    let vis = input.vis.clone();
+    let retained_attrs = retained_attrs(input);
 
     let cfg_ident = format_ident!("{}Cfg", struct_ident);
     let layout_ident = format_ident!("{}Layout", struct_ident);
@@ -126,28 +622,85 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
     let cview_ident = format_ident!("{}ConstView", struct_ident);
     let struct_name = struct_ident.to_string();
 
-    let cleaned_fields: Vec<syn::Field> = fields.iter().map(strip_contig_attrs).collect();
-
     let mut cfg_fields = Vec::new();
+    let mut cfg_clones = Vec::new();
+    let mut clone_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cfg_defaults = Vec::new();
+    let mut default_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cfg_debug_fields = Vec::new();
+    let mut cfg_eq_members = Vec::new();
+    let mut cfg_stored = Vec::new();
+    let mut layout_stored = Vec::new();
+    let mut cfg_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cfg_eq_bounds = Vec::<syn::WherePredicate>::new();
+    let mut layout_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut layout_eq_bounds = Vec::<syn::WherePredicate>::new();
+    let mut view_debug_fields = Vec::new();
+    let mut view_debug_bounds = Vec::<syn::WherePredicate>::new();
     let mut layout_struct_fields = Vec::new();
     let mut layout_inits = Vec::new();
     let mut layout_builders = Vec::new();
+    let mut try_layout_builders = Vec::new();
     let mut view_methods_mut = Vec::new();
     let mut view_methods_const = Vec::new();
+    let mut split_fields = Vec::new();
+    let mut split_builders = Vec::new();
+    let mut split_inits = Vec::new();
+    let mut schema_children = Vec::new();
+    let mut copy_fields = Vec::new();
     let mut contig_bounds = Vec::<syn::WherePredicate>::new();
+    let mut static_bounds = Vec::<syn::WherePredicate>::new();
+    let mut static_layout_inits = Vec::new();
+    let mut static_offset = quote! { 0usize };
+    let mut cfg_index = 0;
+    let mut value_fields = Vec::new();
+    let mut value_bounds = Vec::<syn::WherePredicate>::new();
+    let mut value_cfg_inits = Vec::new();
+    let mut value_empty_cfgs = Vec::new();
+    let mut value_packs = Vec::new();
+    let mut value_unpacks = Vec::new();
+    let mut value_members = Vec::new();
+    let mut value_trait_bounds = Vec::<syn::WherePredicate>::new();
+    let mut value_index = 0;
 
-    for field in fields.iter() {
-        parse_flags(&field.attrs);
-        let fname = field.ident.clone().expect("named field");
+    for (index, field) in fields.iter().enumerate() {
+        let flags = parse_flags(&field.attrs)?;
+        check_markers(&flags, &field.ty, scalar_ty)?;
+        if flags.skip.is_some() {
+            continue;
+        }
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(index)),
+        };
+        // Layout members use the plain index for positional fields (`off_0`, `layout_0`).
+        let (fname, key) = match (&flags.rename, &field.ident) {
+            (Some(name), _) | (None, Some(name)) => (name.clone(), name.unraw().to_string()),
+            (None, None) => (format_ident!("_{}", index), index.to_string()),
+        };
         let fty = &field.ty;
-        let off_ident = format_ident!("off_{}", fname);
-        let lay_ident = format_ident!("layout_{}", fname);
-        let fname_str = fname.to_string();
-        let cfg_field_doc = format!(
-            "Runtime configuration for `{}::{}`.",
-            struct_name.as_str(),
-            fname_str
-        );
+        let off_ident = format_ident!("off_{}", key);
+        let lay_ident = format_ident!("layout_{}", key);
+        let fname_str = member.to_token_stream().to_string();
+        // Values keep every laid-out field, positional ones renumbered past skipped fields.
+        let (value_name, value_member) = match &fields {
+            Fields::Named(_) => (Some(quote! { #fname: }), syn::Member::Named(fname.clone())),
+            _ => (None, syn::Member::Unnamed(syn::Index::from(value_index))),
+        };
+        value_index += 1;
+        let cfg_field_doc = match &flags.fixed_len {
+            Some(len) => format!(
+                "Element configuration for `{}::{}` (fixed length `{}`).",
+                struct_name.as_str(),
+                fname_str,
+                len.to_token_stream()
+            ),
+            None => format!(
+                "Runtime configuration for `{}::{}`.",
+                struct_name.as_str(),
+                fname_str
+            ),
+        };
         let offset_doc = format!(
             "Scalar range covering `{}::{}` inside the buffer.",
             struct_name.as_str(),
@@ -168,11 +721,83 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
             struct_name.as_str(),
             fname_str
         );
+        let mut_field_doc = format!(
+            "Mutable view into `{}::{}`.",
+            struct_name.as_str(),
+            fname_str
+        );
 
-        cfg_fields.push(quote! {
-            #[doc = #cfg_field_doc]
-            pub #fname: <#fty as contig_core::Contig<#scalar_ty>>::Config
-        });
+        // `fixed_len` fields are configured per element; the length is baked in.
+        let cfg_ty = match &flags.fixed_len {
+            Some(_) => {
+                let elem_ty = dyn_elem_type(fty).ok_or_else(|| {
+                    syn::Error::new(
+                        fty.span(),
+                        "`fixed_len` requires a field of type `Dyn<[T]>`",
+                    )
+                })?;
+                contig_bounds.push(parse_quote! {
+                    <#elem_ty as contig_core::Contig<#scalar_ty>>::Config: core::clone::Clone
+                });
+                quote! { <#elem_ty as contig_core::Contig<#scalar_ty>>::Config }
+            }
+            None => quote! { <#fty as contig_core::Contig<#scalar_ty>>::Config },
+        };
+        let field_cfg = if flags.default.is_some() {
+            contig_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::Config: core::default::Default
+            });
+            quote! { &<<#fty as contig_core::Contig<#scalar_ty>>::Config as core::default::Default>::default() }
+        } else {
+            // Positional configs only hold the fields that are configured at runtime.
+            let (cfg_name, cfg_member) = match &fields {
+                Fields::Named(_) => (Some(quote! { #fname: }), syn::Member::Named(fname.clone())),
+                _ => (None, syn::Member::Unnamed(syn::Index::from(cfg_index))),
+            };
+            cfg_index += 1;
+            let segment = fname.unraw().to_string();
+            let value_cfg = quote! {
+                <#fty as contig_core::ContigValue<#scalar_ty>>::try_config_of(&value.#value_member)
+                    .map_err(|err| err.within(contig_core::Segment::Field(#segment)))?
+            };
+            let empty_cfg =
+                quote! { <#fty as contig_core::ContigValue<#scalar_ty>>::empty_config() };
+            value_cfg_inits.push(match &flags.fixed_len {
+                Some(_) => quote! { #cfg_member: #value_cfg.elem },
+                None => quote! { #cfg_member: #value_cfg },
+            });
+            value_empty_cfgs.push(match &flags.fixed_len {
+                Some(_) => quote! { #cfg_member: #empty_cfg.elem },
+                None => quote! { #cfg_member: #empty_cfg },
+            });
+            cfg_fields.push(quote! {
+                #[doc = #cfg_field_doc]
+                pub #cfg_name #cfg_ty
+            });
+            cfg_clones.push(quote! { #cfg_member: core::clone::Clone::clone(&self.#cfg_member) });
+            clone_bounds.push(parse_quote! { #cfg_ty: core::clone::Clone });
+            cfg_defaults.push(quote! { #cfg_member: core::default::Default::default() });
+            let cfg_name = cfg_name.as_ref().map(|_| fname.unraw().to_string());
+            cfg_debug_fields.push(match &cfg_name {
+                Some(name) => quote! { .field(#name, &self.#cfg_member) },
+                None => quote! { .field(&self.#cfg_member) },
+            });
+            cfg_eq_members.push(cfg_member.clone());
+            cfg_stored.push(cfg_ty.to_token_stream());
+            cfg_debug_bounds.push(parse_quote! { for<'__contig> #cfg_ty: core::fmt::Debug });
+            cfg_eq_bounds.push(parse_quote! { for<'__contig> #cfg_ty: core::cmp::PartialEq });
+            // Deferred like the static bounds, so configs without a default still compile.
+            default_bounds.push(parse_quote! { for<'__contig> #cfg_ty: core::default::Default });
+            match &flags.fixed_len {
+                Some(len) => quote! {
+                    &contig_core::DynArrayConfig {
+                        len: #len,
+                        elem: core::clone::Clone::clone(&cfg.#cfg_member),
+                    }
+                },
+                None => quote! { &cfg.#cfg_member },
+            }
+        };
 
         layout_struct_fields.push(quote! {
             #[doc = #offset_doc]
@@ -185,12 +810,46 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
 
         layout_inits.push(quote! { #off_ident });
         layout_inits.push(quote! { #lay_ident });
+        layout_stored.push(quote! { <#fty as contig_core::Contig<#scalar_ty>>::Layout });
+        layout_debug_bounds.push(parse_quote! {
+            for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Layout: core::fmt::Debug
+        });
+        layout_eq_bounds.push(parse_quote! {
+            for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Layout: core::cmp::PartialEq
+        });
+
+        // Both view kinds print through read-only field views, named like the user struct.
+        let field_view = quote! {
+            <#fty as contig_core::Contig<#scalar_ty>>::view(
+                &self.layout.#lay_ident,
+                &self.base[self.layout.#off_ident.clone()],
+            )
+        };
+        view_debug_fields.push(match &fields {
+            Fields::Named(_) => {
+                let name = fname.unraw().to_string();
+                quote! { .field(#name, &#field_view) }
+            }
+            _ => quote! { .field(&#field_view) },
+        });
+        view_debug_bounds.push(parse_quote! {
+            for<'__b> <#fty as contig_core::Contig<#scalar_ty>>::ConstView<'__b>: core::fmt::Debug
+        });
+
+        clone_bounds.push(parse_quote! {
+            <#fty as contig_core::Contig<#scalar_ty>>::Layout: core::clone::Clone
+        });
 
         layout_builders.push(quote! {
-            let #lay_ident = <#fty as contig_core::Contig<#scalar_ty>>::layout(&cfg.#fname);
+            let #lay_ident = <#fty as contig_core::Contig<#scalar_ty>>::layout(#field_cfg);
             let #off_ident = __cursor
                 .take_range(<#fty as contig_core::Contig<#scalar_ty>>::len(&#lay_ident));
         });
+        try_layout_builders.push(quote! {
+            let #lay_ident = <#fty as contig_core::Contig<#scalar_ty>>::try_layout(#field_cfg)?;
+            let #off_ident = __cursor
+                .try_take_range(<#fty as contig_core::Contig<#scalar_ty>>::len(&#lay_ident))?;
+        });
 
         view_methods_mut.push(quote! {
             #[doc = #mut_method_doc]
@@ -211,11 +870,93 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         });
 
+        split_fields.push(quote! {
+            #[doc = #mut_field_doc]
+            pub #fname: <#fty as contig_core::Contig<#scalar_ty>>::MutView<'a>
+        });
+        // Layout ranges are ordered and disjoint, so each field is peeled off the front.
+        split_builders.push(quote! {
+            let (_, __rest) = __rest.split_at_mut(__layout.#off_ident.start - __pos);
+            let (#fname, __rest) = __rest.split_at_mut(__layout.#off_ident.len());
+            let __pos = __layout.#off_ident.end;
+            let #fname = <#fty as contig_core::Contig<#scalar_ty>>::view_mut(
+                &__layout.#lay_ident,
+                #fname,
+            );
+        });
+        split_inits.push(fname.clone());
+        let segment = fname.unraw().to_string();
+        schema_children.push(quote! {
+            <#fty as contig_core::Contig<#scalar_ty>>::schema(
+                &self.#lay_ident,
+                offset + self.#off_ident.start,
+            )
+            .with_segment(contig_core::Segment::Field(#segment))
+        });
+        copy_fields.push(quote! {
+            <#fty as contig_core::Contig<#scalar_ty>>::copy_overlap(
+                &src_layout.#lay_ident,
+                &src[src_layout.#off_ident.clone()],
+                &dst_layout.#lay_ident,
+                &mut dst[dst_layout.#off_ident.clone()],
+            );
+        });
+        let value_field_doc = format!("Owned value of `{}::{}`.", struct_name.as_str(), fname_str);
+        value_fields.push(quote! {
+            #[doc = #value_field_doc]
+            pub #value_name <#fty as contig_core::ContigValue<#scalar_ty>>::Value
+        });
+        value_packs.push(quote! {
+            <#fty as contig_core::ContigValue<#scalar_ty>>::pack(
+                &value.#value_member,
+                &layout.#lay_ident,
+                &mut buf[layout.#off_ident.clone()],
+            );
+        });
+        value_unpacks.push(quote! {
+            #value_member: <#fty as contig_core::ContigValue<#scalar_ty>>::unpack(
+                &layout.#lay_ident,
+                &buf[layout.#off_ident.clone()],
+            )
+        });
+        value_bounds.push(parse_quote! {
+            #fty: contig_core::ContigValue<#scalar_ty>
+        });
+        value_trait_bounds.push(value_field_bound(scalar_ty, fty));
+        value_members.push(value_member);
+
         contig_bounds.push(parse_quote! {
             #fty: contig_core::Contig<#scalar_ty>
         });
+
+        // The dummy higher-ranked lifetime keeps the bound from being checked eagerly, so a
+        // struct with runtime-sized fields still compiles and simply never becomes static.
+        static_bounds.push(parse_quote! {
+            for<'__contig> #fty: contig_core::ContigStatic<#scalar_ty>
+        });
+        let static_len = quote! { <#fty as contig_core::ContigStatic<#scalar_ty>>::LEN };
+        static_layout_inits.push(quote! {
+            #off_ident: (#static_offset)..(#static_offset + #static_len)
+        });
+        static_layout_inits.push(quote! {
+            #lay_ident: <#fty as contig_core::ContigStatic<#scalar_ty>>::LAYOUT
+        });
+        static_offset = quote! { #static_offset + #static_len };
     }
 
+    // Every generated item carries the user's generics plus the `Contig` bounds its field
+    // projections need; views additionally borrow for `'a`.
+    let generics = item_generics(input, &contig_bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let view_generics = view_generics(&generics);
+    let (view_impl_generics, view_ty_generics, _) = view_generics.split_for_impl();
+    let clone_generics = with_predicates(&generics, clone_bounds);
+    let (_, _, clone_where_clause) = clone_generics.split_for_impl();
+    let static_generics = with_predicates(&generics, static_bounds);
+    let (_, _, static_where_clause) = static_generics.split_for_impl();
+    let default_generics = with_predicates(&generics, default_bounds);
+    let (_, _, default_where_clause) = default_generics.split_for_impl();
+
     let cfg_doc = format!(
         "Runtime configuration for `{}` produced by `#[contig]`.",
         struct_name.as_str()
@@ -240,41 +981,166 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
         struct_name.as_str()
     );
     let layout_len_method_doc = "Total scalar footprint of this layout.";
+    let layout_is_empty_doc = "Whether this layout spans no scalar elements.";
+    let layout_try_from_config_doc = format!(
+        "Compute the layout for `{}`, reporting overflow instead of panicking.",
+        struct_name.as_str()
+    );
     let layout_view_doc = "Create a mutable view into the supplied buffer.";
     let layout_cview_doc = "Create a read-only view into the supplied buffer.";
+    let layout_try_view_doc = "Create a mutable view, failing if the buffer is too short.";
+    let layout_schema_doc = "Reflect this layout as a tree of named field ranges.";
+    let layout_relayout_doc = "Lay out `cfg` and carry over the fields `base` (laid out by \
+                               `self`) has in common with it; new slots hold `Default::default()`.";
+    let layout_path_of_doc = "Path of the field holding scalar `index`, e.g. `links[3].pos.y`.";
+    let layout_range_of_doc = "Absolute scalar range named by a path such as `links[2].pos`.";
+    let layout_flat_names_doc =
+        "One name per scalar slot in buffer order, such as `links.0.pos.x`.";
+    let layout_schema_hash_doc = "Fingerprint of this layout's field names, kinds, and sizes and \
+                                  of the scalar type that is stable across builds, for rejecting \
+                                  foreign buffers.";
+    let layout_get_path_doc = "Borrow the scalars named by `path` from the buffer.";
+    let layout_get_path_mut_doc = "Mutably borrow the scalars named by `path` from the buffer.";
+    let layout_schema_at_doc =
+        "Reflect this layout as a tree of named field ranges starting at scalar `offset`.";
+    let layout_try_cview_doc = "Create a read-only view, failing if the buffer is too short.";
 
+    // The user struct is a type-level description only; its fields are never read.
     let struct_definition = {
-        let attrs = &retained_attrs;
-        quote! {
-            #( #attrs )*
-            #vis struct #struct_ident {
-                #( #cleaned_fields ),*
+        let mut cleaned = input.clone();
+        cleaned.attrs = retained_attrs;
+        cleaned.attrs.push(parse_quote!(#[allow(dead_code)]));
+        if let Data::Struct(data) = &mut cleaned.data {
+            for field in data.fields.iter_mut() {
+                *field = strip_contig_attrs(field);
             }
         }
+        cleaned.into_token_stream()
     };
 
+    let debug_ctor = match fields {
+        Fields::Named(_) => quote! { debug_struct },
+        _ => quote! { debug_tuple },
+    };
+    let cfg_name_str = cfg_ident.to_string();
+    let cfg_debug_generics = with_predicates(&generics, cfg_debug_bounds);
+    let (_, _, cfg_debug_where_clause) = cfg_debug_generics.split_for_impl();
+    let cfg_eq_generics = with_predicates(&generics, cfg_eq_bounds);
+    let (_, _, cfg_eq_where_clause) = cfg_eq_generics.split_for_impl();
+    let cfg_traits = quote! {
+        impl #impl_generics core::fmt::Debug for #cfg_ident #ty_generics #cfg_debug_where_clause {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.#debug_ctor(#cfg_name_str)
+                    #( #cfg_debug_fields )*
+                    .finish()
+            }
+        }
+
+        impl #impl_generics core::cmp::PartialEq for #cfg_ident #ty_generics #cfg_eq_where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                let _ = other;
+                true #( && self.#cfg_eq_members == other.#cfg_eq_members )*
+            }
+        }
+    };
+
+    let layout_name_str = layout_ident.to_string();
+    let layout_debug_generics = with_predicates(&generics, layout_debug_bounds);
+    let (_, _, layout_debug_where_clause) = layout_debug_generics.split_for_impl();
+    let layout_eq_generics = with_predicates(&generics, layout_eq_bounds);
+    let (_, _, layout_eq_where_clause) = layout_eq_generics.split_for_impl();
+    let layout_member_names = layout_inits.iter().map(|ident| ident.to_string());
+    let layout_traits = quote! {
+        impl #impl_generics core::fmt::Debug for #layout_ident #ty_generics
+        #layout_debug_where_clause
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(#layout_name_str)
+                    #( .field(#layout_member_names, &self.#layout_inits) )*
+                    .field("len", &self.len)
+                    .finish()
+            }
+        }
+
+        impl #impl_generics core::cmp::PartialEq for #layout_ident #ty_generics
+        #layout_eq_where_clause
+        {
+            fn eq(&self, other: &Self) -> bool {
+                #( self.#layout_inits == other.#layout_inits && )* self.len == other.len
+            }
+        }
+    };
+
+    // The config mirrors the shape of the user struct.
+    let cfg_body = match fields {
+        Fields::Named(_) => quote! { #where_clause { #( #cfg_fields, )* } },
+        Fields::Unnamed(_) => quote! { ( #( #cfg_fields, )* ) #where_clause; },
+        Fields::Unit => quote! { #where_clause; },
+    };
+    let cfg_item = with_serde(
+        serde_attrs(&cfg_stored),
+        quote! {
+            #[doc = #cfg_doc]
+            #vis struct #cfg_ident #generics #cfg_body
+        },
+    );
     let cfg_definition = quote! {
-        #[doc = #cfg_doc]
-        #[derive(Clone)]
-        #vis struct #cfg_ident {
-            #( #cfg_fields, )*
+        #cfg_item
+
+        #cfg_traits
+
+        impl #impl_generics core::clone::Clone for #cfg_ident #ty_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #( #cfg_clones, )*
+                }
+            }
+        }
+
+        impl #impl_generics core::default::Default for #cfg_ident #ty_generics
+        #default_where_clause
+        {
+            fn default() -> Self {
+                Self {
+                    #( #cfg_defaults, )*
+                }
+            }
         }
     };
 
+    let layout_clones = layout_inits
+        .iter()
+        .map(|ident| quote! { #ident: core::clone::Clone::clone(&self.#ident) });
+    let layout_item = with_serde(
+        serde_attrs(&layout_stored),
+        quote! {
+            #[doc = #layout_doc]
+            #vis struct #layout_ident #generics #where_clause {
+                #( #layout_struct_fields, )*
+                #[doc = #layout_len_doc]
+                pub len: usize,
+            }
+        },
+    );
     let layout_definition = quote! {
-        #[doc = #layout_doc]
-        #[derive(Clone)]
-        #vis struct #layout_ident {
-            #( #layout_struct_fields, )*
-            #[doc = #layout_len_doc]
-            pub len: usize,
+        #layout_item
+
+        impl #impl_generics core::clone::Clone for #layout_ident #ty_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #( #layout_clones, )*
+                    len: self.len,
+                }
+            }
         }
+
+        #layout_traits
     };
 
     let layout_impl = quote! {
-        impl #layout_ident {
+        impl #impl_generics #layout_ident #ty_generics #where_clause {
             #[doc = #layout_from_config_doc]
-            pub fn from_config(cfg: &#cfg_ident) -> Self {
+            pub fn from_config(cfg: &#cfg_ident #ty_generics) -> Self {
                 let mut __cursor = contig_core::TakeCursor::new();
                 #( #layout_builders )*
                 let len = __cursor.finish();
@@ -284,67 +1150,248 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
                 }
             }
 
+            #[doc = #layout_try_from_config_doc]
+            pub fn try_from_config(
+                cfg: &#cfg_ident #ty_generics,
+            ) -> Result<Self, contig_core::ContigError> {
+                let mut __cursor = contig_core::TakeCursor::new();
+                #( #try_layout_builders )*
+                let len = __cursor.finish();
+                Ok(Self {
+                    #( #layout_inits, )*
+                    len,
+                })
+            }
+
             #[inline]
             #[doc = #layout_len_method_doc]
             pub fn len(&self) -> usize {
                 self.len
             }
 
-            #[doc = #layout_view_doc]
-            pub fn view<'a>(
-                &'a self,
-                base: &'a mut [#scalar_ty],
-            ) -> #view_ident<'a>
-            where
-                #scalar_ty: 'a,
-            {
-                assert!(base.len() >= self.len, "buffer too small for layout");
-                #view_ident { base, layout: self }
+            #[inline]
+            #[doc = #layout_is_empty_doc]
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
             }
 
-            #[doc = #layout_cview_doc]
-            pub fn cview<'a>(
-                &'a self,
-                base: &'a [#scalar_ty],
-            ) -> #cview_ident<'a>
+            #[doc = #layout_relayout_doc]
+            pub fn relayout(
+                &self,
+                base: &[#scalar_ty],
+                cfg: &#cfg_ident #ty_generics,
+            ) -> (Self, Vec<#scalar_ty>)
             where
-                #scalar_ty: 'a,
+                #scalar_ty: core::clone::Clone + core::default::Default,
             {
-                assert!(base.len() >= self.len, "buffer too small for layout");
-                #cview_ident { base, layout: self }
+                contig_core::relayout::<#scalar_ty, #struct_ident #ty_generics>(self, base, cfg)
             }
-        }
-    };
 
-    let view_definition = quote! {
-        #[doc = #view_doc]
-        #vis struct #view_ident<'a> {
-            base: &'a mut [#scalar_ty],
-            layout: &'a #layout_ident,
-        }
+            #[doc = #layout_schema_doc]
+            pub fn schema(&self) -> contig_core::SchemaNode {
+                self.schema_at(0)
+            }
+
+            #[doc = #layout_path_of_doc]
+            pub fn path_of(&self, index: usize) -> Option<String> {
+                self.schema().path_of(index)
+            }
+
+            #[doc = #layout_flat_names_doc]
+            pub fn flat_names(&self) -> Vec<String> {
+                self.schema().flat_names()
+            }
+
+            #[doc = #layout_schema_hash_doc]
+            pub fn schema_hash(&self) -> u64 {
+                self.schema().scalar_schema_hash::<#scalar_ty>()
+            }
+
+            #[doc = #layout_range_of_doc]
+            pub fn range_of(
+                &self,
+                path: &str,
+            ) -> Result<core::ops::Range<usize>, contig_core::ContigError> {
+                self.schema().range_of(path)
+            }
+
+            #[doc = #layout_get_path_doc]
+            pub fn get_path<'a>(
+                &self,
+                base: &'a [#scalar_ty],
+                path: &str,
+            ) -> Result<&'a [#scalar_ty], contig_core::ContigError> {
+                self.schema().get_path(base, path)
+            }
+
+            #[doc = #layout_get_path_mut_doc]
+            pub fn get_path_mut<'a>(
+                &self,
+                base: &'a mut [#scalar_ty],
+                path: &str,
+            ) -> Result<&'a mut [#scalar_ty], contig_core::ContigError> {
+                self.schema().get_path_mut(base, path)
+            }
+
+            #[doc = #layout_schema_at_doc]
+            pub fn schema_at(&self, offset: usize) -> contig_core::SchemaNode {
+                contig_core::SchemaNode::new(
+                    contig_core::SchemaKind::Struct,
+                    offset..offset + self.len,
+                )
+                .with_children(vec![#( #schema_children ),*])
+            }
+
+            #[doc = #layout_view_doc]
+            pub fn view<'a>(
+                &'a self,
+                base: &'a mut [#scalar_ty],
+            ) -> #view_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+            {
+                assert!(base.len() >= self.len, "buffer too small for layout");
+                #view_ident {
+                    base,
+                    layout: contig_core::LayoutRef::Borrowed(self),
+                }
+            }
+
+            #[doc = #layout_cview_doc]
+            pub fn cview<'a>(
+                &'a self,
+                base: &'a [#scalar_ty],
+            ) -> #cview_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+            {
+                assert!(base.len() >= self.len, "buffer too small for layout");
+                #cview_ident {
+                    base,
+                    layout: contig_core::LayoutRef::Borrowed(self),
+                }
+            }
+
+            #[doc = #layout_try_view_doc]
+            pub fn try_view<'a>(
+                &'a self,
+                base: &'a mut [#scalar_ty],
+            ) -> Result<#view_ident #view_ty_generics, contig_core::ContigError>
+            where
+                #scalar_ty: 'a,
+            {
+                contig_core::ContigError::check_len(self.len, base.len())?;
+                Ok(self.view(base))
+            }
+
+            #[doc = #layout_try_cview_doc]
+            pub fn try_cview<'a>(
+                &'a self,
+                base: &'a [#scalar_ty],
+            ) -> Result<#cview_ident #view_ty_generics, contig_core::ContigError>
+            where
+                #scalar_ty: 'a,
+            {
+                contig_core::ContigError::check_len(self.len, base.len())?;
+                Ok(self.cview(base))
+            }
+        }
+    };
+
+    let view_definition = quote! {
+        #[doc = #view_doc]
+        #vis struct #view_ident #view_generics #where_clause {
+            base: &'a mut [#scalar_ty],
+            layout: contig_core::LayoutRef<'a, #layout_ident #ty_generics>,
+        }
     };
 
     let const_view_definition = quote! {
         #[doc = #cview_doc]
-        #vis struct #cview_ident<'a> {
+        #vis struct #cview_ident #view_generics #where_clause {
             base: &'a [#scalar_ty],
-            layout: &'a #layout_ident,
+            layout: contig_core::LayoutRef<'a, #layout_ident #ty_generics>,
         }
     };
 
+    let type_args: Vec<_> = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Type(param) => param.ident.to_token_stream(),
+            syn::GenericParam::Const(param) => param.ident.to_token_stream(),
+            syn::GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        })
+        .collect();
+    // Unit structs and structs whose fields are all skipped have nothing to split.
+    let (fields_mut_definition, fields_mut_method) = if split_fields.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        let fields_mut_ident = format_ident!("{}FieldsMut", struct_ident);
+        let fields_mut_doc = format!(
+            "Simultaneous mutable views into every field of `{}`.",
+            struct_name.as_str()
+        );
+        let fields_mut_method_doc = "Borrow every field at once as disjoint mutable views.";
+        (
+            quote! {
+                #[doc = #fields_mut_doc]
+                #vis struct #fields_mut_ident #view_generics #where_clause {
+                    #( #split_fields, )*
+                }
+            },
+            quote! {
+                #[doc = #fields_mut_method_doc]
+                pub fn fields_mut(&mut self) -> #fields_mut_ident<'_, #( #type_args ),*> {
+                    let __layout: &#layout_ident #ty_generics = &self.layout;
+                    let __rest: &mut [#scalar_ty] = &mut *self.base;
+                    let __pos = 0usize;
+                    #( #split_builders )*
+                    #fields_mut_ident {
+                        #( #split_inits, )*
+                    }
+                }
+            },
+        )
+    };
+
     let view_impl = quote! {
-        impl<'a> #view_ident<'a> {
+        impl #view_impl_generics #view_ident #view_ty_generics #where_clause {
             #[inline]
             #[doc = #view_as_mut_slice_doc]
             pub fn as_mut_slice(&mut self) -> &mut [#scalar_ty] {
                 self.base
             }
+            #fields_mut_method
             #( #view_methods_mut )*
         }
     };
 
+    let view_debug_generics = with_predicates(&generics, view_debug_bounds);
+    let (_, _, view_debug_where_clause) = view_debug_generics.split_for_impl();
+    let view_debug_body = quote! {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.#debug_ctor(#struct_name)
+                #( #view_debug_fields )*
+                .finish()
+        }
+    };
+    let view_debug_impls = quote! {
+        impl #view_impl_generics core::fmt::Debug for #view_ident #view_ty_generics
+        #view_debug_where_clause
+        {
+            #view_debug_body
+        }
+
+        impl #view_impl_generics core::fmt::Debug for #cview_ident #view_ty_generics
+        #view_debug_where_clause
+        {
+            #view_debug_body
+        }
+    };
+
     let const_view_impl = quote! {
-        impl<'a> #cview_ident<'a> {
+        impl #view_impl_generics #cview_ident #view_ty_generics #where_clause {
             #[inline]
             #[doc = #const_view_as_slice_doc]
             pub fn as_slice(&self) -> &[#scalar_ty] {
@@ -354,31 +1401,189 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
-    let const_view_type = quote! { #cview_ident<'a> };
-    let view_type = quote! { #view_ident<'a> };
+    // Arithmetic covers exactly the scalars of this struct, even when the view was built
+    // over a longer buffer.
+    let real_generics = with_predicates(
+        &generics,
+        [parse_quote! { for<'__contig> #scalar_ty: contig_core::Real }],
+    );
+    let (_, _, real_where_clause) = real_generics.split_for_impl();
+    let other_view = quote! { #cview_ident<'_, #( #type_args ),*> };
+    let fill_doc = "Set every scalar of this value to `value`.";
+    let copy_from_doc = "Overwrite this value with `src`.";
+    let axpy_doc = "`self += alpha * x`, scalar by scalar.";
+    let scale_doc = "`self *= alpha`, scalar by scalar.";
+    let dot_doc = "Dot product with `other` over every scalar of the value.";
+    let norm_doc = "Euclidean norm over every scalar of the value.";
+    // Views serialize through the layout schema, so nested fields keep their names.
+    let serde_view_impls = {
+        let serde_generics = with_predicates(
+            &generics,
+            [parse_quote! { for<'__contig> #scalar_ty: contig_core::serde::Serialize }],
+        );
+        let (_, _, serde_where_clause) = serde_generics.split_for_impl();
+        let serialize_body = quote! {
+            fn serialize<__S: contig_core::serde::Serializer>(
+                &self,
+                serializer: __S,
+            ) -> Result<__S::Ok, __S::Error> {
+                let schema = self.layout.schema();
+                contig_core::serde::Serialize::serialize(
+                    &contig_core::SchemaSerialize::new(&schema, self.base),
+                    serializer,
+                )
+            }
+        };
+        let deserialize_from_doc = "Overwrite this value with the structured form its \
+                                    `Serialize` impl writes, failing if the shapes differ.";
+        let de_generics = deserialize_view_generics(
+            &view_generics,
+            [parse_quote! { #scalar_ty: contig_core::serde::Deserialize<'de> }],
+        );
+        let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+        let impls = quote! {
+            impl #view_impl_generics contig_core::serde::Serialize for #view_ident #view_ty_generics
+            #serde_where_clause
+            {
+                #serialize_body
+            }
+
+            impl #view_impl_generics contig_core::serde::Serialize for #cview_ident #view_ty_generics
+            #serde_where_clause
+            {
+                #serialize_body
+            }
+
+            impl #view_impl_generics #view_ident #view_ty_generics #where_clause {
+                #[doc = #deserialize_from_doc]
+                pub fn deserialize_from<'de, __D>(&mut self, deserializer: __D) -> Result<(), __D::Error>
+                where
+                    __D: contig_core::serde::Deserializer<'de>,
+                    #scalar_ty: contig_core::serde::Deserialize<'de>,
+                {
+                    let schema = self.layout.schema();
+                    contig_core::serde::de::DeserializeSeed::deserialize(
+                        contig_core::SchemaSeed::new(&schema, self.base),
+                        deserializer,
+                    )
+                }
+            }
+
+            impl #de_impl_generics contig_core::DeserializeView<'de> for #view_ident #view_ty_generics
+            #de_where_clause
+            {
+                fn deserialize_from<__D: contig_core::serde::Deserializer<'de>>(
+                    &mut self,
+                    deserializer: __D,
+                ) -> Result<(), __D::Error> {
+                    #view_ident::deserialize_from(self, deserializer)
+                }
+            }
+        };
+        with_serde(impls, quote! {})
+    };
+
+    let vector_ops_impl = quote! {
+        impl #view_impl_generics #view_ident #view_ty_generics #real_where_clause {
+            #[doc = #fill_doc]
+            pub fn fill(&mut self, value: #scalar_ty) {
+                contig_core::ops::fill(&mut self.base[..self.layout.len], value);
+            }
+
+            #[doc = #copy_from_doc]
+            pub fn copy_from(&mut self, src: &#other_view) {
+                contig_core::ops::copy_from(
+                    &mut self.base[..self.layout.len],
+                    &src.base[..src.layout.len],
+                );
+            }
+
+            #[doc = #axpy_doc]
+            pub fn axpy(&mut self, alpha: #scalar_ty, x: &#other_view) {
+                contig_core::ops::axpy(
+                    alpha,
+                    &x.base[..x.layout.len],
+                    &mut self.base[..self.layout.len],
+                );
+            }
+
+            #[doc = #scale_doc]
+            pub fn scale(&mut self, alpha: #scalar_ty) {
+                contig_core::ops::scale(alpha, &mut self.base[..self.layout.len]);
+            }
+
+            #[doc = #dot_doc]
+            pub fn dot(&self, other: &#other_view) -> #scalar_ty {
+                contig_core::ops::dot(&self.base[..self.layout.len], &other.base[..other.layout.len])
+            }
+
+            #[doc = #norm_doc]
+            pub fn norm(&self) -> #scalar_ty {
+                contig_core::ops::norm(&self.base[..self.layout.len])
+            }
+        }
+
+        impl #view_impl_generics #cview_ident #view_ty_generics #real_where_clause {
+            #[doc = #dot_doc]
+            pub fn dot(&self, other: &#other_view) -> #scalar_ty {
+                contig_core::ops::dot(&self.base[..self.layout.len], &other.base[..other.layout.len])
+            }
 
-    let contig_where_clause = if contig_bounds.is_empty() {
-        None
+            #[doc = #norm_doc]
+            pub fn norm(&self) -> #scalar_ty {
+                contig_core::ops::norm(&self.base[..self.layout.len])
+            }
+        }
+    };
+
+    let copy_unused = if copy_fields.is_empty() {
+        quote! { let _ = (src_layout, src, dst_layout, dst); }
     } else {
-        let preds = contig_bounds.iter();
-        Some(quote! { where #( #preds ),* })
+        quote! {}
     };
 
+    let const_view_type = quote! { #cview_ident #view_ty_generics };
+    let view_type = quote! { #view_ident #view_ty_generics };
+
     let contig_impl = quote! {
-        impl contig_core::Contig<#scalar_ty> for #struct_ident #contig_where_clause {
-            type Config = #cfg_ident;
-            type Layout = #layout_ident;
-            type ConstView<'a> = #const_view_type;
-            type MutView<'a> = #view_type;
+        impl #impl_generics contig_core::Contig<#scalar_ty> for #struct_ident #ty_generics
+        #where_clause
+        {
+            type Config = #cfg_ident #ty_generics;
+            type Layout = #layout_ident #ty_generics;
+            type ConstView<'a> = #const_view_type where #scalar_ty: 'a, Self::Layout: 'a;
+            type MutView<'a> = #view_type where #scalar_ty: 'a, Self::Layout: 'a;
 
             fn layout(config: &Self::Config) -> Self::Layout {
-                #layout_ident::from_config(config)
+                <#layout_ident #ty_generics>::from_config(config)
+            }
+
+            fn try_layout(
+                config: &Self::Config,
+            ) -> Result<Self::Layout, contig_core::ContigError> {
+                <#layout_ident #ty_generics>::try_from_config(config)
             }
 
             fn len(layout: &Self::Layout) -> usize {
                 layout.len()
             }
 
+            fn schema(layout: &Self::Layout, offset: usize) -> contig_core::SchemaNode {
+                layout.schema_at(offset)
+            }
+
+            fn copy_overlap(
+                src_layout: &Self::Layout,
+                src: &[#scalar_ty],
+                dst_layout: &Self::Layout,
+                dst: &mut [#scalar_ty],
+            ) where
+                #scalar_ty: core::clone::Clone,
+            {
+                #copy_unused
+                #( #copy_fields )*
+            }
+
             fn view<'a>(
                 layout: &'a Self::Layout,
                 buf: &'a [#scalar_ty],
@@ -395,17 +1600,1268 @@ pub fn contig(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
+    let static_impl = quote! {
+        impl #impl_generics contig_core::ContigStatic<#scalar_ty> for #struct_ident #ty_generics
+        #static_where_clause
+        {
+            const LEN: usize = #static_offset;
+            const LAYOUT: Self::Layout = #layout_ident {
+                #( #static_layout_inits, )*
+                len: #static_offset,
+            };
+
+            fn view_static<'a>(buf: &'a [#scalar_ty]) -> Self::ConstView<'a>
+            where
+                #scalar_ty: 'a,
+                Self::Layout: 'a,
+            {
+                assert!(buf.len() >= Self::LEN, "buffer too small for layout");
+                #cview_ident {
+                    base: buf,
+                    layout: contig_core::LayoutRef::Owned(Self::LAYOUT),
+                }
+            }
+
+            fn view_static_mut<'a>(buf: &'a mut [#scalar_ty]) -> Self::MutView<'a>
+            where
+                #scalar_ty: 'a,
+                Self::Layout: 'a,
+            {
+                assert!(buf.len() >= Self::LEN, "buffer too small for layout");
+                #view_ident {
+                    base: buf,
+                    layout: contig_core::LayoutRef::Owned(Self::LAYOUT),
+                }
+            }
+        }
+    };
+
+    // `owned` adds a plain struct mirroring the fields, converted to and from the buffer.
+    let value_definition = if args.owned {
+        let value_ident = format_ident!("{}Value", struct_ident);
+        let value_name_str = value_ident.to_string();
+        let value_generics = with_predicates(&generics, value_bounds);
+        let (_, _, value_where_clause) = value_generics.split_for_impl();
+        let value_doc = format!(
+            "Owned value of `{}` produced by `#[contig(owned)]`.",
+            struct_name.as_str()
+        );
+        let value_trait_generics = with_predicates(&value_generics, value_trait_bounds);
+        let (_, _, value_trait_where_clause) = value_trait_generics.split_for_impl();
+        let value_debug = match fields {
+            Fields::Named(_) => {
+                let names = value_members.iter().map(|member| match member {
+                    syn::Member::Named(ident) => ident.unraw().to_string(),
+                    syn::Member::Unnamed(index) => index.index.to_string(),
+                });
+                quote! {
+                    f.debug_struct(#value_name_str)
+                        #( .field(#names, &self.#value_members) )*
+                        .finish()
+                }
+            }
+            _ => quote! {
+                f.debug_tuple(#value_name_str)
+                    #( .field(&self.#value_members) )*
+                    .finish()
+            },
+        };
+        let value_traits = quote! {
+            impl #impl_generics core::clone::Clone for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn clone(&self) -> Self {
+                    Self {
+                        #( #value_members: core::clone::Clone::clone(&self.#value_members), )*
+                    }
+                }
+            }
+
+            impl #impl_generics core::fmt::Debug for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    #value_debug
+                }
+            }
+
+            impl #impl_generics core::cmp::PartialEq for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    let _ = other;
+                    true #( && self.#value_members == other.#value_members )*
+                }
+            }
+        };
+        let value_body = match fields {
+            Fields::Named(_) => quote! { #value_where_clause { #( #value_fields, )* } },
+            Fields::Unnamed(_) => quote! { ( #( #value_fields, )* ) #value_where_clause; },
+            Fields::Unit => quote! { #value_where_clause; },
+        };
+        let config_of_doc = "Config whose layout fits this value, with `Dyn` lengths taken \
+                             from it.\n\nPanics if the elements of an array field need \
+                             different configs (see [`contig_core::ContigValue::try_config_of`]).";
+        let pack_doc = "Write this value into a view laid out for [`Self::config_of`].";
+        let unpack_doc = "Read an owned value back out of a view.";
+        let cfg_unused = value_cfg_inits
+            .is_empty()
+            .then(|| quote! { let _ = value; });
+        let pack_unused = value_packs
+            .is_empty()
+            .then(|| quote! { let _ = (value, layout, buf); });
+        let unpack_unused = value_unpacks
+            .is_empty()
+            .then(|| quote! { let _ = (layout, buf); });
+        quote! {
+            #[doc = #value_doc]
+            #vis struct #value_ident #generics #value_body
+
+            #value_traits
+
+            impl #impl_generics #value_ident #ty_generics #value_where_clause {
+                #[doc = #config_of_doc]
+                pub fn config_of(&self) -> #cfg_ident #ty_generics {
+                    <#struct_ident #ty_generics as contig_core::ContigValue<#scalar_ty>>::config_of(
+                        self,
+                    )
+                }
+
+                #[doc = #pack_doc]
+                pub fn pack(&self, view: &mut #view_ident<'_, #( #type_args ),*>) {
+                    <#struct_ident #ty_generics as contig_core::ContigValue<#scalar_ty>>::pack(
+                        self,
+                        &view.layout,
+                        view.base,
+                    );
+                }
+
+                #[doc = #unpack_doc]
+                pub fn unpack(view: &#cview_ident<'_, #( #type_args ),*>) -> Self {
+                    <#struct_ident #ty_generics as contig_core::ContigValue<#scalar_ty>>::unpack(
+                        &view.layout,
+                        view.base,
+                    )
+                }
+            }
+
+            impl #impl_generics contig_core::ContigValue<#scalar_ty> for #struct_ident #ty_generics
+            #value_where_clause
+            {
+                type Value = #value_ident #ty_generics;
+
+                fn config_of(value: &Self::Value) -> Self::Config {
+                    Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+                }
+
+                fn try_config_of(
+                    value: &Self::Value,
+                ) -> Result<Self::Config, contig_core::ContigError> {
+                    #cfg_unused
+                    Ok(#cfg_ident {
+                        #( #value_cfg_inits, )*
+                    })
+                }
+
+                fn empty_config() -> Self::Config {
+                    #cfg_ident {
+                        #( #value_empty_cfgs, )*
+                    }
+                }
+
+                fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [#scalar_ty]) {
+                    #pack_unused
+                    #( #value_packs )*
+                }
+
+                fn unpack(layout: &Self::Layout, buf: &[#scalar_ty]) -> Self::Value {
+                    #unpack_unused
+                    #value_ident {
+                        #( #value_unpacks, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let expanded = quote! {
         #struct_definition
         #cfg_definition
         #layout_definition
         #layout_impl
         #view_definition
+        #fields_mut_definition
         #const_view_definition
         #view_impl
         #const_view_impl
+        #view_debug_impls
+        #serde_view_impls
+        #vector_ops_impl
         #contig_impl
+        #static_impl
+        #value_definition
     };
 
-    expanded.into()
+    Ok(expanded)
+}
+
+/// Expand an enum whose variants carry named fields (or none) into a tagged union.
+///
+/// The variant is chosen in the generated `Cfg`, so the tag lives in the layout rather than
+/// the buffer and the footprint matches the selected variant. Views are enums of per-variant
+/// field views, carved into disjoint borrows up front so they can be `match`ed directly.
+fn expand_enum(
+    args: &ItemArgs,
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let scalar_ty = &args.scalar_ty;
+    let enum_ident = &input.ident;
+    let vis = &input.vis;
+    let retained_attrs = retained_attrs(input);
+    let enum_name = enum_ident.to_string();
+
+    let cfg_ident = format_ident!("{}Cfg", enum_ident);
+    let layout_ident = format_ident!("{}Layout", enum_ident);
+    let view_ident = format_ident!("{}View", enum_ident);
+    let cview_ident = format_ident!("{}ConstView", enum_ident);
+
+    let mut cleaned_variants = Vec::new();
+    let mut cfg_variants = Vec::new();
+    let mut layout_variants = Vec::new();
+    let mut view_variants = Vec::new();
+    let mut cview_variants = Vec::new();
+    let mut cfg_clone_arms = Vec::new();
+    let mut layout_clone_arms = Vec::new();
+    let mut from_config_arms = Vec::new();
+    let mut try_from_config_arms = Vec::new();
+    let mut len_arms = Vec::new();
+    let mut view_arms = Vec::new();
+    let mut schema_arms = Vec::new();
+    let mut copy_arms = Vec::new();
+    let mut cview_arms = Vec::new();
+    let mut value_variants = Vec::new();
+    let mut value_cfg_arms = Vec::new();
+    // Config of the first variant, for elements of empty arrays.
+    let mut empty_cfg = None;
+    let mut value_pack_arms = Vec::new();
+    let mut value_unpack_arms = Vec::new();
+    let mut value_bounds = Vec::<syn::WherePredicate>::new();
+    let mut value_trait_bounds = Vec::<syn::WherePredicate>::new();
+    let mut value_clone_arms = Vec::new();
+    let mut value_debug_arms = Vec::new();
+    let mut value_eq_arms = Vec::new();
+    let mut cfg_debug_arms = Vec::new();
+    let mut cfg_eq_arms = Vec::new();
+    let mut layout_debug_arms = Vec::new();
+    let mut layout_eq_arms = Vec::new();
+    let mut view_debug_arms = Vec::new();
+    let mut view_serialize_arms = Vec::new();
+    let mut view_serialize_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cview_serialize_bounds = Vec::<syn::WherePredicate>::new();
+    let mut view_deserialize_bounds = Vec::<syn::WherePredicate>::new();
+    let mut view_deserialize_arms = Vec::new();
+    let mut cfg_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cfg_eq_bounds = Vec::<syn::WherePredicate>::new();
+    let mut layout_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut layout_eq_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cfg_stored = Vec::new();
+    let mut layout_stored = Vec::new();
+    let mut view_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut cview_debug_bounds = Vec::<syn::WherePredicate>::new();
+    let mut contig_bounds = Vec::<syn::WherePredicate>::new();
+    let mut clone_bounds = Vec::<syn::WherePredicate>::new();
+    let mut has_fields = false;
+
+    for variant in &data.variants {
+        let vname = &variant.ident;
+        let fields: Vec<&syn::Field> = match &variant.fields {
+            Fields::Named(named) => named.named.iter().collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "#[contig] enum variants need named fields",
+                ));
+            }
+        };
+        has_fields |= !fields.is_empty();
+
+        let mut cleaned = variant.clone();
+        for field in cleaned.fields.iter_mut() {
+            field.attrs.retain(|attr| !attr.path().is_ident("contig"));
+        }
+        cleaned_variants.push(cleaned);
+
+        let mut fnames = Vec::new();
+        let mut off_idents = Vec::new();
+        let mut lay_idents = Vec::new();
+        let mut cfg_fields = Vec::new();
+        let mut layout_fields = Vec::new();
+        let mut view_fields = Vec::new();
+        let mut cview_fields = Vec::new();
+        let mut layout_builders = Vec::new();
+        let mut try_layout_builders = Vec::new();
+        let mut view_builders = Vec::new();
+        let mut schema_children = Vec::new();
+        let mut copy_fields = Vec::new();
+        let mut copy_bindings = Vec::new();
+        let mut cview_builders = Vec::new();
+        let mut value_fields = Vec::new();
+        let mut value_cfgs = Vec::new();
+        let mut value_empty_cfgs = Vec::new();
+        let mut value_packs = Vec::new();
+        let mut value_unpacks = Vec::new();
+
+        for field in &fields {
+            let flags = parse_flags(&field.attrs)?;
+            check_markers(&flags, &field.ty, scalar_ty)?;
+            // Variant configs are matched as a whole, so only naming keys apply here.
+            let struct_only = [
+                flags.skip.map(|span| (span, "skip")),
+                flags.default.map(|span| (span, "default")),
+                flags
+                    .fixed_len
+                    .as_ref()
+                    .map(|len| (len.span(), "fixed_len")),
+            ];
+            if let Some((span, key)) = struct_only.into_iter().flatten().next() {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{}` is only supported on struct fields", key),
+                ));
+            }
+            let fname = flags
+                .rename
+                .unwrap_or_else(|| field.ident.clone().expect("named field"));
+            let fty = &field.ty;
+            let off_ident = format_ident!("off_{}", fname);
+            let lay_ident = format_ident!("layout_{}", fname);
+            let path = format!("{}::{}::{}", enum_name, vname, fname);
+            let cfg_field_doc = format!("Runtime configuration for `{}`.", path);
+            let offset_doc = format!("Scalar range covering `{}` inside the buffer.", path);
+            let layout_field_doc = format!("Layout metadata for `{}`.", path);
+            let mut_field_doc = format!("Mutable view into `{}`.", path);
+            let const_field_doc = format!("Read-only view into `{}`.", path);
+
+            cfg_fields.push(quote! {
+                #[doc = #cfg_field_doc]
+                #fname: <#fty as contig_core::Contig<#scalar_ty>>::Config
+            });
+            layout_fields.push(quote! {
+                #[doc = #offset_doc]
+                #off_ident: core::ops::Range<usize>
+            });
+            layout_fields.push(quote! {
+                #[doc = #layout_field_doc]
+                #lay_ident: <#fty as contig_core::Contig<#scalar_ty>>::Layout
+            });
+            view_fields.push(quote! {
+                #[doc = #mut_field_doc]
+                #fname: <#fty as contig_core::Contig<#scalar_ty>>::MutView<'a>
+            });
+            cview_fields.push(quote! {
+                #[doc = #const_field_doc]
+                #fname: <#fty as contig_core::Contig<#scalar_ty>>::ConstView<'a>
+            });
+
+            layout_builders.push(quote! {
+                let #lay_ident = <#fty as contig_core::Contig<#scalar_ty>>::layout(#fname);
+                let #off_ident = __cursor
+                    .take_range(<#fty as contig_core::Contig<#scalar_ty>>::len(&#lay_ident));
+            });
+            try_layout_builders.push(quote! {
+                let #lay_ident = <#fty as contig_core::Contig<#scalar_ty>>::try_layout(#fname)?;
+                let #off_ident = __cursor
+                    .try_take_range(<#fty as contig_core::Contig<#scalar_ty>>::len(&#lay_ident))?;
+            });
+            let segment = fname.unraw().to_string();
+            schema_children.push(quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::schema(#lay_ident, offset + #off_ident.start)
+                    .with_segment(contig_core::Segment::Field(#segment))
+            });
+            let src_off = format_ident!("src_{}", off_ident);
+            let src_lay = format_ident!("src_{}", lay_ident);
+            let dst_off = format_ident!("dst_{}", off_ident);
+            let dst_lay = format_ident!("dst_{}", lay_ident);
+            copy_fields.push(quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::copy_overlap(
+                    #src_lay,
+                    &src[#src_off.clone()],
+                    #dst_lay,
+                    &mut dst[#dst_off.clone()],
+                );
+            });
+            copy_bindings.push(quote! { #off_ident: #src_off, #lay_ident: #src_lay });
+            copy_bindings.push(quote! { #off_ident: #dst_off, #lay_ident: #dst_lay });
+            let value_field_doc = format!("Owned value of `{}`.", path);
+            value_fields.push(quote! {
+                #[doc = #value_field_doc]
+                #fname: <#fty as contig_core::ContigValue<#scalar_ty>>::Value
+            });
+            let variant_name = vname.unraw().to_string();
+            value_cfgs.push(quote! {
+                #fname: <#fty as contig_core::ContigValue<#scalar_ty>>::try_config_of(#fname)
+                    .map_err(|err| {
+                        err.within(contig_core::Segment::Field(#segment))
+                            .within(contig_core::Segment::Field(#variant_name))
+                    })?
+            });
+            value_empty_cfgs.push(quote! {
+                #fname: <#fty as contig_core::ContigValue<#scalar_ty>>::empty_config()
+            });
+            value_packs.push(quote! {
+                <#fty as contig_core::ContigValue<#scalar_ty>>::pack(
+                    #fname,
+                    #lay_ident,
+                    &mut buf[#off_ident.clone()],
+                );
+            });
+            value_unpacks.push(quote! {
+                #fname: <#fty as contig_core::ContigValue<#scalar_ty>>::unpack(
+                    #lay_ident,
+                    &buf[#off_ident.clone()],
+                )
+            });
+            value_bounds.push(parse_quote! {
+                #fty: contig_core::ContigValue<#scalar_ty>
+            });
+            value_trait_bounds.push(value_field_bound(scalar_ty, fty));
+            cfg_stored.push(quote! { <#fty as contig_core::Contig<#scalar_ty>>::Config });
+            layout_stored.push(quote! { <#fty as contig_core::Contig<#scalar_ty>>::Layout });
+            cfg_debug_bounds.push(parse_quote! {
+                for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Config: core::fmt::Debug
+            });
+            layout_debug_bounds.push(parse_quote! {
+                for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Layout: core::fmt::Debug
+            });
+            cfg_eq_bounds.push(parse_quote! {
+                for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Config:
+                    core::cmp::PartialEq
+            });
+            layout_eq_bounds.push(parse_quote! {
+                for<'__contig> <#fty as contig_core::Contig<#scalar_ty>>::Layout:
+                    core::cmp::PartialEq
+            });
+            view_debug_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::MutView<'a>: core::fmt::Debug
+            });
+            cview_debug_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::ConstView<'a>: core::fmt::Debug
+            });
+            view_serialize_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::MutView<'a>: contig_core::serde::Serialize
+            });
+            cview_serialize_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::ConstView<'a>:
+                    contig_core::serde::Serialize
+            });
+            view_deserialize_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::MutView<'a>:
+                    contig_core::DeserializeView<'de>
+            });
+            view_builders.push(quote! {
+                let (#fname, __rest) = __rest.split_at_mut(#off_ident.len());
+                let #fname = <#fty as contig_core::Contig<#scalar_ty>>::view_mut(#lay_ident, #fname);
+            });
+            cview_builders.push(quote! {
+                let (#fname, __rest) = __rest.split_at(#off_ident.len());
+                let #fname = <#fty as contig_core::Contig<#scalar_ty>>::view(#lay_ident, #fname);
+            });
+
+            contig_bounds.push(parse_quote! {
+                #fty: contig_core::Contig<#scalar_ty>
+            });
+            clone_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::Config: core::clone::Clone
+            });
+            clone_bounds.push(parse_quote! {
+                <#fty as contig_core::Contig<#scalar_ty>>::Layout: core::clone::Clone
+            });
+
+            fnames.push(fname);
+            off_idents.push(off_ident);
+            lay_idents.push(lay_ident);
+        }
+
+        let cfg_variant_doc = format!("Configuration selecting `{}::{}`.", enum_name, vname);
+        let layout_variant_doc = format!("Layout metadata for `{}::{}`.", enum_name, vname);
+        let view_variant_doc = format!(
+            "Mutable views into the fields of `{}::{}`.",
+            enum_name, vname
+        );
+        let cview_variant_doc = format!(
+            "Read-only views into the fields of `{}::{}`.",
+            enum_name, vname
+        );
+        cfg_variants.push(quote! {
+            #[doc = #cfg_variant_doc]
+            #vname { #( #cfg_fields, )* }
+        });
+        layout_variants.push(quote! {
+            #[doc = #layout_variant_doc]
+            #vname { #( #layout_fields, )* }
+        });
+        view_variants.push(quote! {
+            #[doc = #view_variant_doc]
+            #vname { #( #view_fields, )* }
+        });
+        cview_variants.push(quote! {
+            #[doc = #cview_variant_doc]
+            #vname { #( #cview_fields, )* }
+        });
+
+        cfg_clone_arms.push(quote! {
+            Self::#vname { #( #fnames ),* } => Self::#vname {
+                #( #fnames: core::clone::Clone::clone(#fnames), )*
+            }
+        });
+        layout_clone_arms.push(quote! {
+            Self::#vname { #( #off_idents, #lay_idents ),* } => Self::#vname {
+                #( #off_idents: core::clone::Clone::clone(#off_idents), )*
+                #( #lay_idents: core::clone::Clone::clone(#lay_idents), )*
+            }
+        });
+        from_config_arms.push(quote! {
+            #cfg_ident::#vname { #( #fnames ),* } => {
+                let mut __cursor = contig_core::TakeCursor::new();
+                #( #layout_builders )*
+                let _ = __cursor;
+                Self::#vname { #( #off_idents, #lay_idents ),* }
+            }
+        });
+        try_from_config_arms.push(quote! {
+            #cfg_ident::#vname { #( #fnames ),* } => {
+                let mut __cursor = contig_core::TakeCursor::new();
+                #( #try_layout_builders )*
+                let _ = __cursor;
+                Ok(Self::#vname { #( #off_idents, #lay_idents ),* })
+            }
+        });
+        len_arms.push(match off_idents.last() {
+            Some(last) => quote! { Self::#vname { #last, .. } => #last.end },
+            None => quote! { Self::#vname { .. } => 0 },
+        });
+        let variant_name = vname.unraw().to_string();
+        schema_arms.push(quote! {
+            Self::#vname { #( #off_idents, #lay_idents ),* } => contig_core::SchemaNode::new(
+                contig_core::SchemaKind::Enum { variant: #variant_name },
+                offset..offset + self.len(),
+            )
+            .with_children(vec![#( #schema_children ),*])
+        });
+        let value_variant_doc = format!("Owned value of `{}::{}`.", enum_name, vname);
+        value_variants.push(quote! {
+            #[doc = #value_variant_doc]
+            #vname { #( #value_fields, )* }
+        });
+        value_clone_arms.push(quote! {
+            Self::#vname { #( #fnames ),* } => Self::#vname {
+                #( #fnames: core::clone::Clone::clone(#fnames), )*
+            }
+        });
+        // Configs, values, and views share one shape per variant: the named fields.
+        let layout_members: Vec<_> = off_idents
+            .iter()
+            .zip(&lay_idents)
+            .flat_map(|(off, lay)| [off.clone(), lay.clone()])
+            .collect();
+        cfg_debug_arms.push(debug_arm(vname, &fnames));
+        cfg_eq_arms.push(eq_arm(vname, &fnames));
+        layout_debug_arms.push(debug_arm(vname, &layout_members));
+        layout_eq_arms.push(eq_arm(vname, &layout_members));
+        view_debug_arms.push(debug_arm(vname, &fnames));
+        let variant_index = view_serialize_arms.len() as u32;
+        let variant_name = vname.unraw().to_string();
+        let field_names = fnames.iter().map(|fname| fname.unraw().to_string());
+        let field_count = fnames.len();
+        view_serialize_arms.push(quote! {
+            Self::#vname { #( #fnames ),* } => {
+                #[allow(unused_mut)]
+                let mut state = serializer.serialize_struct_variant(
+                    #enum_name,
+                    #variant_index,
+                    #variant_name,
+                    #field_count,
+                )?;
+                #( state.serialize_field(#field_names, #fnames)?; )*
+                state.end()
+            }
+        });
+        // Each field view reads its own value; a local struct generic over the view types
+        // lets the shared visitor dispatch to them by position.
+        let field_names = fnames.iter().map(|fname| fname.unraw().to_string());
+        let view_params: Vec<_> = (0..field_count)
+            .map(|i| format_ident!("__V{}", i))
+            .collect();
+        let positions = (0..field_count).map(syn::Index::from);
+        let indices = 0..field_count;
+        view_deserialize_arms.push(if fnames.is_empty() {
+            quote! {
+                Self::#vname {} => contig_core::deserialize_variant(deserializer, #variant_name, ())
+            }
+        } else {
+            quote! {
+                Self::#vname { #( #fnames ),* } => {
+                    struct __Fields<'r, #( #view_params ),*>(#( &'r mut #view_params ),*);
+
+                    impl<'de, #( #view_params: contig_core::DeserializeView<'de> ),*>
+                        contig_core::FieldSeeds<'de> for __Fields<'_, #( #view_params ),*>
+                    {
+                        fn name(&self, index: usize) -> Option<&'static str> {
+                            [#( #field_names ),*].get(index).copied()
+                        }
+
+                        fn next_value<__A: contig_core::serde::de::MapAccess<'de>>(
+                            &mut self,
+                            index: usize,
+                            map: &mut __A,
+                        ) -> Result<(), __A::Error> {
+                            match index {
+                                #( #indices => map.next_value_seed(
+                                    contig_core::ViewSeed(&mut *self.#positions),
+                                ), )*
+                                _ => unreachable!("field index out of range"),
+                            }
+                        }
+                    }
+
+                    contig_core::deserialize_variant(
+                        deserializer,
+                        #variant_name,
+                        __Fields(#( #fnames ),*),
+                    )
+                }
+            }
+        });
+        value_debug_arms.push(debug_arm(vname, &fnames));
+        value_eq_arms.push(eq_arm(vname, &fnames));
+        value_cfg_arms.push(quote! {
+            Self::Value::#vname { #( #fnames ),* } => #cfg_ident::#vname { #( #value_cfgs, )* }
+        });
+        if empty_cfg.is_none() {
+            empty_cfg = Some(quote! { #cfg_ident::#vname { #( #value_empty_cfgs, )* } });
+        }
+        value_pack_arms.push(quote! {
+            (
+                Self::Value::#vname { #( #fnames ),* },
+                #layout_ident::#vname { #( #off_idents, #lay_idents ),* },
+            ) => {
+                #( #value_packs )*
+            }
+        });
+        value_unpack_arms.push(quote! {
+            #layout_ident::#vname { #( #off_idents, #lay_idents ),* } => Self::Value::#vname {
+                #( #value_unpacks, )*
+            }
+        });
+        let src_bindings = copy_bindings.iter().step_by(2);
+        let dst_bindings = copy_bindings.iter().skip(1).step_by(2);
+        copy_arms.push(quote! {
+            (
+                #layout_ident::#vname { #( #src_bindings ),* },
+                #layout_ident::#vname { #( #dst_bindings ),* },
+            ) => {
+                #( #copy_fields )*
+            }
+        });
+        view_arms.push(quote! {
+            Self::#vname { #( #off_idents, #lay_idents ),* } => {
+                let __rest = base;
+                #( #view_builders )*
+                let _ = __rest;
+                #view_ident::#vname { #( #fnames ),* }
+            }
+        });
+        cview_arms.push(quote! {
+            Self::#vname { #( #off_idents, #lay_idents ),* } => {
+                let __rest = base;
+                #( #cview_builders )*
+                let _ = __rest;
+                #cview_ident::#vname { #( #fnames ),* }
+            }
+        });
+    }
+
+    if !has_fields {
+        return Err(syn::Error::new(
+            input.span(),
+            "#[contig] enums need at least one variant with fields",
+        ));
+    }
+
+    let generics = item_generics(input, &contig_bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let view_generics = view_generics(&generics);
+    let (view_impl_generics, view_ty_generics, _) = view_generics.split_for_impl();
+    let clone_generics = with_predicates(&generics, clone_bounds);
+    let (_, _, clone_where_clause) = clone_generics.split_for_impl();
+    let cfg_debug_generics = with_predicates(&generics, cfg_debug_bounds);
+    let (_, _, cfg_debug_where_clause) = cfg_debug_generics.split_for_impl();
+    let cfg_eq_generics = with_predicates(&generics, cfg_eq_bounds);
+    let (_, _, cfg_eq_where_clause) = cfg_eq_generics.split_for_impl();
+    let layout_debug_generics = with_predicates(&generics, layout_debug_bounds);
+    let (_, _, layout_debug_where_clause) = layout_debug_generics.split_for_impl();
+    let layout_eq_generics = with_predicates(&generics, layout_eq_bounds);
+    let (_, _, layout_eq_where_clause) = layout_eq_generics.split_for_impl();
+    let view_debug_generics = with_predicates(&generics, view_debug_bounds);
+    let (_, _, view_debug_where_clause) = view_debug_generics.split_for_impl();
+    let cview_debug_generics = with_predicates(&generics, cview_debug_bounds);
+    let (_, _, cview_debug_where_clause) = cview_debug_generics.split_for_impl();
+    // Enum views hold only their fields' views, so they serialize as serde struct variants.
+    let serde_view_impls = {
+        let view_serialize_generics = with_predicates(&generics, view_serialize_bounds);
+        let (_, _, view_serialize_where_clause) = view_serialize_generics.split_for_impl();
+        let cview_serialize_generics = with_predicates(&generics, cview_serialize_bounds);
+        let (_, _, cview_serialize_where_clause) = cview_serialize_generics.split_for_impl();
+        let serialize_body = quote! {
+            fn serialize<__S: contig_core::serde::Serializer>(
+                &self,
+                serializer: __S,
+            ) -> Result<__S::Ok, __S::Error> {
+                use contig_core::serde::ser::SerializeStructVariant;
+                match self {
+                    #( #view_serialize_arms )*
+                }
+            }
+        };
+        let deserialize_from_doc = "Overwrite the fields of the active variant with the \
+                                    structured form `Serialize` writes, failing if the variant \
+                                    or the shapes differ.";
+        let de_generics = deserialize_view_generics(&view_generics, view_deserialize_bounds);
+        let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+        let de_predicates = de_where_clause.map(|clause| &clause.predicates);
+        let impls = quote! {
+            impl #view_impl_generics contig_core::serde::Serialize for #view_ident #view_ty_generics
+            #view_serialize_where_clause
+            {
+                #serialize_body
+            }
+
+            impl #view_impl_generics contig_core::serde::Serialize for #cview_ident #view_ty_generics
+            #cview_serialize_where_clause
+            {
+                #serialize_body
+            }
+
+            impl #view_impl_generics #view_ident #view_ty_generics #where_clause {
+                #[doc = #deserialize_from_doc]
+                pub fn deserialize_from<'de, __D>(&mut self, deserializer: __D) -> Result<(), __D::Error>
+                where
+                    __D: contig_core::serde::Deserializer<'de>,
+                    #de_predicates
+                {
+                    match self {
+                        #( #view_deserialize_arms, )*
+                    }
+                }
+            }
+
+            impl #de_impl_generics contig_core::DeserializeView<'de> for #view_ident #view_ty_generics
+            #de_where_clause
+            {
+                fn deserialize_from<__D: contig_core::serde::Deserializer<'de>>(
+                    &mut self,
+                    deserializer: __D,
+                ) -> Result<(), __D::Error> {
+                    #view_ident::deserialize_from(self, deserializer)
+                }
+            }
+        };
+        with_serde(impls, quote! {})
+    };
+
+    let cfg_doc = format!(
+        "Runtime configuration for `{}` produced by `#[contig]`; the variant picks the layout.",
+        enum_name
+    );
+    let layout_doc = format!(
+        "Layout metadata for the selected variant of `{}` computed by `#[contig]`.",
+        enum_name
+    );
+    let view_doc = format!(
+        "Mutable per-variant views over `{}` borrowed from a contiguous buffer.",
+        enum_name
+    );
+    let cview_doc = format!(
+        "Read-only per-variant views over `{}` borrowed from a contiguous buffer.",
+        enum_name
+    );
+    let layout_from_config_doc = format!(
+        "Compute the layout for the variant of `{}` selected by `cfg`.",
+        enum_name
+    );
+    let layout_len_method_doc = "Total scalar footprint of the selected variant.";
+    let layout_is_empty_doc = "Whether the selected variant spans no scalar elements.";
+    let layout_view_doc = "Split the supplied buffer into mutable views of the variant's fields.";
+    let layout_cview_doc =
+        "Split the supplied buffer into read-only views of the variant's fields.";
+    let layout_try_from_config_doc = format!(
+        "Compute the layout for the selected variant of `{}`, reporting overflow instead of \
+         panicking.",
+        enum_name
+    );
+    let layout_try_view_doc = "Split into mutable views, failing if the buffer is too short.";
+    let layout_schema_doc = "Reflect the selected variant as a tree of named field ranges.";
+    let layout_relayout_doc = "Lay out `cfg` and carry over the fields `base` (laid out by \
+                               `self`) has in common with it when the variant is unchanged; \
+                               new slots hold `Default::default()`.";
+    let layout_path_of_doc = "Path of the field holding scalar `index`, e.g. `angle`.";
+    let layout_range_of_doc = "Absolute scalar range named by a path such as `links[2].pos`.";
+    let layout_flat_names_doc =
+        "One name per scalar slot in buffer order, such as `links.0.pos.x`.";
+    let layout_schema_hash_doc = "Fingerprint of this layout's field names, kinds, and sizes and \
+                                  of the scalar type that is stable across builds, for rejecting \
+                                  foreign buffers.";
+    let layout_get_path_doc = "Borrow the scalars named by `path` from the buffer.";
+    let layout_get_path_mut_doc = "Mutably borrow the scalars named by `path` from the buffer.";
+    let layout_schema_at_doc = "Reflect the selected variant as a tree of named field ranges \
+                                starting at scalar `offset`.";
+    let layout_try_cview_doc = "Split into read-only views, failing if the buffer is too short.";
+
+    let input_generics = &input.generics;
+    let input_where_clause = &input.generics.where_clause;
+
+    // `owned` adds a plain enum mirroring the variants, converted to and from the buffer.
+    let empty_cfg = empty_cfg.unwrap_or_else(|| {
+        let message = format!("`{}` has no variants to configure", enum_name);
+        quote! { panic!(#message) }
+    });
+    let value_definition = if args.owned {
+        let value_ident = format_ident!("{}Value", enum_ident);
+        let value_generics = with_predicates(&generics, value_bounds);
+        let (_, _, value_where_clause) = value_generics.split_for_impl();
+        let value_trait_generics = with_predicates(&value_generics, value_trait_bounds);
+        let (_, _, value_trait_where_clause) = value_trait_generics.split_for_impl();
+        let value_doc = format!(
+            "Owned value of `{}` produced by `#[contig(owned)]`.\n\n\
+             Enum views only hold per-field views, so values are packed through \
+             [`contig_core::ContigValue`] (or [`contig_core::ContigBox::from_value`]).",
+            enum_name
+        );
+        let config_of_doc = "Config selecting this value's variant, with `Dyn` lengths taken \
+                             from it.\n\nPanics if the elements of an array field need \
+                             different configs (see [`contig_core::ContigValue::try_config_of`]).";
+        quote! {
+            #[doc = #value_doc]
+            #vis enum #value_ident #generics #value_where_clause {
+                #( #value_variants, )*
+            }
+
+            impl #impl_generics core::clone::Clone for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn clone(&self) -> Self {
+                    match self {
+                        #( #value_clone_arms, )*
+                    }
+                }
+            }
+
+            impl #impl_generics core::fmt::Debug for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    match self {
+                        #( #value_debug_arms, )*
+                    }
+                }
+            }
+
+            impl #impl_generics core::cmp::PartialEq for #value_ident #ty_generics
+            #value_trait_where_clause
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #( #value_eq_arms, )*
+                        _ => false,
+                    }
+                }
+            }
+
+            impl #impl_generics #value_ident #ty_generics #value_where_clause {
+                #[doc = #config_of_doc]
+                pub fn config_of(&self) -> #cfg_ident #ty_generics {
+                    <#enum_ident #ty_generics as contig_core::ContigValue<#scalar_ty>>::config_of(
+                        self,
+                    )
+                }
+            }
+
+            impl #impl_generics contig_core::ContigValue<#scalar_ty> for #enum_ident #ty_generics
+            #value_where_clause
+            {
+                type Value = #value_ident #ty_generics;
+
+                fn config_of(value: &Self::Value) -> Self::Config {
+                    Self::try_config_of(value).unwrap_or_else(|err| panic!("{}", err))
+                }
+
+                fn try_config_of(
+                    value: &Self::Value,
+                ) -> Result<Self::Config, contig_core::ContigError> {
+                    Ok(match value {
+                        #( #value_cfg_arms, )*
+                    })
+                }
+
+                fn empty_config() -> Self::Config {
+                    #empty_cfg
+                }
+
+                fn pack(value: &Self::Value, layout: &Self::Layout, buf: &mut [#scalar_ty]) {
+                    #[allow(unreachable_patterns)]
+                    match (value, layout) {
+                        #( #value_pack_arms, )*
+                        _ => panic!("value variant does not match layout"),
+                    }
+                }
+
+                fn unpack(layout: &Self::Layout, buf: &[#scalar_ty]) -> Self::Value {
+                    match layout {
+                        #( #value_unpack_arms, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let cfg_item = with_serde(
+        serde_attrs(&cfg_stored),
+        quote! {
+            #[doc = #cfg_doc]
+            #vis enum #cfg_ident #generics #where_clause {
+                #( #cfg_variants, )*
+            }
+        },
+    );
+    let layout_item = with_serde(
+        serde_attrs(&layout_stored),
+        quote! {
+            #[doc = #layout_doc]
+            #vis enum #layout_ident #generics #where_clause {
+                #( #layout_variants, )*
+            }
+        },
+    );
+
+    // The user enum is a type-level description only; it is never constructed.
+    Ok(quote! {
+        #( #retained_attrs )*
+        #[allow(dead_code)]
+        #vis enum #enum_ident #input_generics #input_where_clause {
+            #( #cleaned_variants ),*
+        }
+
+        #cfg_item
+
+        impl #impl_generics core::clone::Clone for #cfg_ident #ty_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                match self {
+                    #( #cfg_clone_arms, )*
+                }
+            }
+        }
+
+        impl #impl_generics core::fmt::Debug for #cfg_ident #ty_generics #cfg_debug_where_clause {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    #( #cfg_debug_arms, )*
+                }
+            }
+        }
+
+        impl #impl_generics core::cmp::PartialEq for #cfg_ident #ty_generics #cfg_eq_where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #( #cfg_eq_arms, )*
+                    _ => false,
+                }
+            }
+        }
+
+        #layout_item
+
+        impl #impl_generics core::clone::Clone for #layout_ident #ty_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                match self {
+                    #( #layout_clone_arms, )*
+                }
+            }
+        }
+
+        impl #impl_generics core::fmt::Debug for #layout_ident #ty_generics
+        #layout_debug_where_clause
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    #( #layout_debug_arms, )*
+                }
+            }
+        }
+
+        impl #impl_generics core::cmp::PartialEq for #layout_ident #ty_generics
+        #layout_eq_where_clause
+        {
+            fn eq(&self, other: &Self) -> bool {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #( #layout_eq_arms, )*
+                    _ => false,
+                }
+            }
+        }
+
+        impl #impl_generics #layout_ident #ty_generics #where_clause {
+            #[doc = #layout_from_config_doc]
+            pub fn from_config(cfg: &#cfg_ident #ty_generics) -> Self {
+                match cfg {
+                    #( #from_config_arms, )*
+                }
+            }
+
+            #[doc = #layout_try_from_config_doc]
+            pub fn try_from_config(
+                cfg: &#cfg_ident #ty_generics,
+            ) -> Result<Self, contig_core::ContigError> {
+                match cfg {
+                    #( #try_from_config_arms, )*
+                }
+            }
+
+            #[inline]
+            #[doc = #layout_len_method_doc]
+            pub fn len(&self) -> usize {
+                match self {
+                    #( #len_arms, )*
+                }
+            }
+
+            #[inline]
+            #[doc = #layout_is_empty_doc]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #[doc = #layout_relayout_doc]
+            pub fn relayout(
+                &self,
+                base: &[#scalar_ty],
+                cfg: &#cfg_ident #ty_generics,
+            ) -> (Self, Vec<#scalar_ty>)
+            where
+                #scalar_ty: core::clone::Clone + core::default::Default,
+            {
+                contig_core::relayout::<#scalar_ty, #enum_ident #ty_generics>(self, base, cfg)
+            }
+
+            #[doc = #layout_schema_doc]
+            pub fn schema(&self) -> contig_core::SchemaNode {
+                self.schema_at(0)
+            }
+
+            #[doc = #layout_path_of_doc]
+            pub fn path_of(&self, index: usize) -> Option<String> {
+                self.schema().path_of(index)
+            }
+
+            #[doc = #layout_flat_names_doc]
+            pub fn flat_names(&self) -> Vec<String> {
+                self.schema().flat_names()
+            }
+
+            #[doc = #layout_schema_hash_doc]
+            pub fn schema_hash(&self) -> u64 {
+                self.schema().scalar_schema_hash::<#scalar_ty>()
+            }
+
+            #[doc = #layout_range_of_doc]
+            pub fn range_of(
+                &self,
+                path: &str,
+            ) -> Result<core::ops::Range<usize>, contig_core::ContigError> {
+                self.schema().range_of(path)
+            }
+
+            #[doc = #layout_get_path_doc]
+            pub fn get_path<'a>(
+                &self,
+                base: &'a [#scalar_ty],
+                path: &str,
+            ) -> Result<&'a [#scalar_ty], contig_core::ContigError> {
+                self.schema().get_path(base, path)
+            }
+
+            #[doc = #layout_get_path_mut_doc]
+            pub fn get_path_mut<'a>(
+                &self,
+                base: &'a mut [#scalar_ty],
+                path: &str,
+            ) -> Result<&'a mut [#scalar_ty], contig_core::ContigError> {
+                self.schema().get_path_mut(base, path)
+            }
+
+            #[doc = #layout_schema_at_doc]
+            pub fn schema_at(&self, offset: usize) -> contig_core::SchemaNode {
+                match self {
+                    #( #schema_arms, )*
+                }
+            }
+
+            #[doc = #layout_view_doc]
+            pub fn view<'a>(
+                &'a self,
+                base: &'a mut [#scalar_ty],
+            ) -> #view_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+            {
+                assert!(base.len() >= self.len(), "buffer too small for layout");
+                match self {
+                    #( #view_arms, )*
+                }
+            }
+
+            #[doc = #layout_cview_doc]
+            pub fn cview<'a>(
+                &'a self,
+                base: &'a [#scalar_ty],
+            ) -> #cview_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+            {
+                assert!(base.len() >= self.len(), "buffer too small for layout");
+                match self {
+                    #( #cview_arms, )*
+                }
+            }
+
+            #[doc = #layout_try_view_doc]
+            pub fn try_view<'a>(
+                &'a self,
+                base: &'a mut [#scalar_ty],
+            ) -> Result<#view_ident #view_ty_generics, contig_core::ContigError>
+            where
+                #scalar_ty: 'a,
+            {
+                contig_core::ContigError::check_len(self.len(), base.len())?;
+                Ok(self.view(base))
+            }
+
+            #[doc = #layout_try_cview_doc]
+            pub fn try_cview<'a>(
+                &'a self,
+                base: &'a [#scalar_ty],
+            ) -> Result<#cview_ident #view_ty_generics, contig_core::ContigError>
+            where
+                #scalar_ty: 'a,
+            {
+                contig_core::ContigError::check_len(self.len(), base.len())?;
+                Ok(self.cview(base))
+            }
+        }
+
+        #[doc = #view_doc]
+        #vis enum #view_ident #view_generics #where_clause {
+            #( #view_variants, )*
+        }
+
+        #[doc = #cview_doc]
+        #vis enum #cview_ident #view_generics #where_clause {
+            #( #cview_variants, )*
+        }
+
+        impl #view_impl_generics core::fmt::Debug for #view_ident #view_ty_generics
+        #view_debug_where_clause
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    #( #view_debug_arms, )*
+                }
+            }
+        }
+
+        impl #view_impl_generics core::fmt::Debug for #cview_ident #view_ty_generics
+        #cview_debug_where_clause
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    #( #view_debug_arms, )*
+                }
+            }
+        }
+
+        #serde_view_impls
+
+        impl #impl_generics contig_core::Contig<#scalar_ty> for #enum_ident #ty_generics
+        #where_clause
+        {
+            type Config = #cfg_ident #ty_generics;
+            type Layout = #layout_ident #ty_generics;
+            type ConstView<'a> = #cview_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+                Self::Layout: 'a;
+            type MutView<'a> = #view_ident #view_ty_generics
+            where
+                #scalar_ty: 'a,
+                Self::Layout: 'a;
+
+            fn layout(config: &Self::Config) -> Self::Layout {
+                <#layout_ident #ty_generics>::from_config(config)
+            }
+
+            fn try_layout(
+                config: &Self::Config,
+            ) -> Result<Self::Layout, contig_core::ContigError> {
+                <#layout_ident #ty_generics>::try_from_config(config)
+            }
+
+            fn len(layout: &Self::Layout) -> usize {
+                layout.len()
+            }
+
+            fn schema(layout: &Self::Layout, offset: usize) -> contig_core::SchemaNode {
+                layout.schema_at(offset)
+            }
+
+            fn copy_overlap(
+                src_layout: &Self::Layout,
+                src: &[#scalar_ty],
+                dst_layout: &Self::Layout,
+                dst: &mut [#scalar_ty],
+            ) where
+                #scalar_ty: core::clone::Clone,
+            {
+                // Only a matching variant has fields in common; otherwise keep the defaults.
+                #[allow(unreachable_patterns)]
+                match (src_layout, dst_layout) {
+                    #( #copy_arms, )*
+                    _ => {}
+                }
+            }
+
+            fn view<'a>(
+                layout: &'a Self::Layout,
+                buf: &'a [#scalar_ty],
+            ) -> Self::ConstView<'a> {
+                layout.cview(buf)
+            }
+
+            fn view_mut<'a>(
+                layout: &'a Self::Layout,
+                buf: &'a mut [#scalar_ty],
+            ) -> Self::MutView<'a> {
+                layout.view(buf)
+            }
+        }
+
+        #value_definition
+    })
 }
@@ -3,9 +3,35 @@ fn ui() {
     let t = trybuild::TestCases::new();
     t.pass("tests/ui/basic.rs");
     t.pass("tests/ui/nested.rs");
+    t.pass("tests/ui/static_footprint.rs");
     t.compile_fail("tests/ui/missing_scalar.rs");
-    t.compile_fail("tests/ui/generic_struct.rs");
+    t.compile_fail("tests/ui/unknown_item_arg.rs");
+    t.compile_fail("tests/ui/unknown_item_value.rs");
+    t.compile_fail("tests/ui/duplicate_item_arg.rs");
+    t.pass("tests/ui/generic_struct.rs");
+    t.pass("tests/ui/enum_variants.rs");
+    t.pass("tests/ui/tuple_struct.rs");
+    t.pass("tests/ui/raw_ident.rs");
+    t.pass("tests/ui/field_attrs.rs");
+    t.pass("tests/ui/fields_mut.rs");
+    t.pass("tests/ui/fallible.rs");
+    t.pass("tests/ui/schema.rs");
+    t.pass("tests/ui/relayout.rs");
+    t.pass("tests/ui/owned.rs");
+    t.pass("tests/ui/vector_ops.rs");
+    t.pass("tests/ui/debug.rs");
+    t.pass("tests/ui/serde.rs");
+    t.compile_fail("tests/ui/lifetime_param.rs");
     t.compile_fail("tests/ui/enum_not_allowed.rs");
+    t.compile_fail("tests/ui/enum_tuple_variant.rs");
     t.compile_fail("tests/ui/wrong_field_attr.rs");
+    t.compile_fail("tests/ui/unknown_field_attr.rs");
+    t.compile_fail("tests/ui/conflicting_field_attrs.rs");
+    t.compile_fail("tests/ui/marker_with_value.rs");
+    t.compile_fail("tests/ui/fixed_len_not_dyn.rs");
+    t.compile_fail("tests/ui/len_not_array.rs");
+    t.compile_fail("tests/ui/elem_shape_fixed.rs");
+    t.compile_fail("tests/ui/enum_struct_only_attr.rs");
     t.compile_fail("tests/ui/missing_contig_import.rs");
+    t.compile_fail("tests/ui/static_dyn_field.rs");
 }
@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Conflict {
+    #[contig(len, fixed_len = 4)]
+    samples: contig_core::Dyn<[f64]>,
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    time: f64,
+}
+
+#[contig(scalar = f64)]
+enum Segment {
+    Line { points: Dyn<[f64]> },
+    Hold,
+}
+
+fn main() {
+    let cfg = RobotCfg { links: DynArrayConfig { len: 1, elem: LinkCfg::default() }, time: () };
+    assert_eq!(cfg, cfg.clone());
+    let layout = RobotLayout::from_config(&cfg);
+    assert_eq!(layout, RobotLayout::from_config(&cfg));
+    assert!(format!("{:?}", layout).starts_with("RobotLayout { "));
+
+    let mut buffer = [10.0, 1.0, 2.0, 3.0, 0.0];
+    let expected = "Robot { links: [Link { mass: 10.0, pos: [1.0, 2.0, 3.0] }], time: 0.0 }";
+    assert_eq!(format!("{:?}", layout.cview(&buffer)), expected);
+    assert_eq!(format!("{:?}", layout.view(&mut buffer)), expected);
+    assert!(format!("{:#?}", layout.cview(&buffer)).contains("\n    links: [\n"));
+
+    let cfg = SegmentCfg::Line { points: DynArrayConfig { len: 2, elem: () } };
+    assert_eq!(format!("{:?}", cfg), "Line { points: DynArrayConfig { len: 2, elem: () } }");
+    assert_ne!(cfg, SegmentCfg::Hold {});
+    let layout = SegmentLayout::from_config(&cfg);
+    assert_eq!(layout, SegmentLayout::from_config(&cfg));
+    let buffer = [1.0, 2.0];
+    assert_eq!(format!("{:?}", layout.cview(&buffer)), "Line { points: [1.0, 2.0] }");
+}
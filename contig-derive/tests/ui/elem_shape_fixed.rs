@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Trace {
+    #[contig(len, elem_shape)]
+    points: Dyn<[[f64; 3]]>,
+}
+
+fn main() {}
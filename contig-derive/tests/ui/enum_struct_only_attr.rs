@@ -0,0 +1,11 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+enum Mode {
+    Active {
+        #[contig(default)]
+        gain: f64,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute(f64),
+    Fixed,
+}
+
+fn main() {}
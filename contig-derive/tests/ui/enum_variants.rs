@@ -0,0 +1,61 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute { angle: f64 },
+    Prismatic { offset: f64, axis: [f64; 3] },
+    Fixed,
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    joints: Dyn<[Joint]>,
+    gain: f64,
+}
+
+fn main() {
+    let layout = JointLayout::from_config(&JointCfg::Prismatic {
+        offset: (),
+        axis: (),
+    });
+    assert_eq!(layout.len(), 4);
+    assert_eq!(JointLayout::from_config(&JointCfg::Revolute { angle: () }).len(), 1);
+    assert!(JointLayout::from_config(&JointCfg::Fixed {}).is_empty());
+
+    let mut buf = vec![0.0f64; layout.len()];
+    match layout.view(&mut buf) {
+        JointView::Prismatic { offset, mut axis } => {
+            // Both fields are borrowed mutably at the same time.
+            *offset = 0.5;
+            *axis.get_mut(2) = 1.0;
+        }
+        _ => panic!("expected the prismatic variant"),
+    }
+    assert_eq!(buf, [0.5, 0.0, 0.0, 1.0]);
+
+    let JointConstView::Prismatic { offset, .. } = layout.cview(&buf) else {
+        panic!("expected the prismatic variant");
+    };
+    assert_eq!(*offset, 0.5);
+
+    // Elements of a `Dyn` array share one config, hence one variant.
+    let robot = RobotLayout::from_config(&RobotCfg {
+        joints: DynArrayConfig {
+            len: 2,
+            elem: JointCfg::Revolute { angle: () },
+        },
+        gain: (),
+    });
+    assert_eq!(robot.len(), 3);
+    let mut buf = vec![0.0f64; robot.len()];
+    {
+        let mut view = robot.view(&mut buf);
+        let mut joints = view.joints();
+        if let JointView::Revolute { angle } = joints.get_mut(1) {
+            *angle = 2.0;
+        }
+    }
+    assert_eq!(buf, [0.0, 2.0, 0.0]);
+}
@@ -0,0 +1,59 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Trajectory {
+    #[contig(len)]
+    knots: Dyn<[[f64; 4]]>,
+    duration: f64,
+}
+
+#[contig(scalar = f64)]
+enum Segment {
+    Line { points: Dyn<[f64]> },
+    Hold,
+}
+
+fn main() {
+    let huge = TrajectoryCfg {
+        knots: DynArrayConfig {
+            len: usize::MAX / 2,
+            elem: (),
+        },
+        duration: (),
+    };
+    assert_eq!(
+        TrajectoryLayout::try_from_config(&huge).err(),
+        Some(ContigError::Overflow)
+    );
+    assert!(<Trajectory as Contig<f64>>::try_layout(&huge).is_err());
+
+    let cfg = TrajectoryCfg {
+        knots: DynArrayConfig { len: 2, elem: () },
+        duration: (),
+    };
+    let layout = TrajectoryLayout::try_from_config(&cfg).unwrap();
+    let mut short = [0.0; 8];
+    assert_eq!(
+        layout.try_view(&mut short).err().map(|err| err.to_string()),
+        Some("buffer too short for layout: expected 9 scalars, got 8".to_owned())
+    );
+    let mut buf = [0.0; 9];
+    *layout.try_view(&mut buf).unwrap().duration() = 3.0;
+    assert_eq!(*layout.try_cview(&buf).unwrap().duration(), 3.0);
+
+    let cfg = SegmentCfg::Line {
+        points: DynArrayConfig {
+            len: usize::MAX,
+            elem: (),
+        },
+    };
+    assert!(SegmentLayout::try_from_config(&cfg).is_ok());
+    let layout = SegmentLayout::try_from_config(&SegmentCfg::Line {
+        points: DynArrayConfig { len: 3, elem: () },
+    })
+    .unwrap();
+    assert!(layout.try_cview(&[0.0; 2]).is_err());
+    assert!(<Segment as Contig<f64>>::try_view(&layout, &[0.0; 3]).is_ok());
+    assert!(SegmentLayout::try_from_config(&SegmentCfg::Hold {}).unwrap().is_empty());
+}
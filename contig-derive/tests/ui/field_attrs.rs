@@ -0,0 +1,63 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Leg {
+    #[contig(skip)]
+    label: String,
+    #[contig(rename = mass_kg)]
+    mass: f64,
+    #[contig(fixed_len = 3)]
+    gains: Dyn<[f64]>,
+    #[contig(default)]
+    offset: [f64; 2],
+    #[contig(len, elem_shape)]
+    samples: Ragged<[Dyn<[f64]>]>,
+}
+
+#[contig(scalar = f64)]
+struct Wrapped(#[contig(skip)] (), #[contig(default)] f64, f64);
+
+#[contig(scalar = f64)]
+enum Actuator {
+    Motor {
+        #[contig(rename = torque_nm)]
+        torque: f64,
+    },
+    Idle,
+}
+
+fn main() {
+    let cfg = LegCfg {
+        mass_kg: (),
+        gains: (),
+        samples: vec![
+            DynArrayConfig { len: 0, elem: () },
+            DynArrayConfig { len: 2, elem: () },
+        ],
+    };
+    let layout = LegLayout::from_config(&cfg);
+    assert_eq!(layout.len(), 1 + 3 + 2 + 2);
+
+    let mut buf = vec![0.0; layout.len()];
+    {
+        let mut view = layout.view(&mut buf);
+        *view.mass_kg() = 4.0;
+        *view.gains().get_mut(2) = 0.5;
+        *view.samples().get_mut(1).get_mut(1) = 9.0;
+    }
+    assert_eq!(buf, [4.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 9.0]);
+
+    let layout = WrappedLayout::from_config(&WrappedCfg(()));
+    let mut buf = [0.0; 2];
+    *layout.view(&mut buf)._2() = 1.0;
+    assert_eq!(*layout.cview(&buf)._1(), 0.0);
+    assert_eq!(buf, [0.0, 1.0]);
+
+    let layout = ActuatorLayout::from_config(&ActuatorCfg::Motor { torque_nm: () });
+    let mut buf = [0.0];
+    if let ActuatorView::Motor { torque_nm } = layout.view(&mut buf) {
+        *torque_nm = 2.0;
+    }
+    assert_eq!(buf, [2.0]);
+}
@@ -0,0 +1,51 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct State {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    #[contig(skip)]
+    _name: String,
+    time: f64,
+    #[contig(len)]
+    scalars: Dyn<[f64]>,
+}
+
+#[contig(scalar = F)]
+struct Pair<F: Copy + 'static>(F, [F; 2]);
+
+fn main() {
+    let cfg = StateCfg {
+        links: DynArrayConfig { len: 2, elem: LinkCfg { mass: (), pos: () } },
+        time: (),
+        scalars: DynArrayConfig { len: 2, elem: () },
+    };
+    let layout = StateLayout::from_config(&cfg);
+    let mut buf = vec![0.0; layout.len()];
+    {
+        let mut view = layout.view(&mut buf);
+        let StateFieldsMut { mut links, time, mut scalars } = view.fields_mut();
+        *time = 0.5;
+        for i in 0..links.len() {
+            *scalars.get_mut(i) = i as f64 + 1.0;
+            *links.get_mut(i).mass() = *scalars.get_mut(i) * 10.0;
+        }
+    }
+    assert_eq!(
+        buf,
+        [10.0, 0.0, 0.0, 0.0, 20.0, 0.0, 0.0, 0.0, 0.5, 1.0, 2.0]
+    );
+
+    let mut buf = [0.0f32; 3];
+    let mut view = Pair::<f32>::view_static_mut(&mut buf);
+    let PairFieldsMut { _0: first, _1: mut rest } = view.fields_mut();
+    *rest.get_mut(1) = *first + 2.0;
+    assert_eq!(buf, [0.0, 0.0, 2.0]);
+}
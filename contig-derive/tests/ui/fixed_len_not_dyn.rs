@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct NotDyn {
+    #[contig(fixed_len = 3)]
+    gains: [f64; 3],
+}
+
+fn main() {}
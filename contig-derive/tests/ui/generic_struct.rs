@@ -1,8 +1,72 @@
+use contig_core::prelude::*;
 use contig_derive::contig;
 
+/// Stand-in for a numeric trait such as `num_traits::Float`.
+trait Float: Copy + 'static + Contig<Self, Config = (), Layout = contig_core::ScalarLayout> {}
+impl Float for f32 {}
+impl Float for f64 {}
+
+#[contig(scalar = F)]
+struct Link<F: Float> {
+    mass: F,
+    pos: [F; 3],
+}
+
+#[contig(scalar = F)]
+struct Robot<F>
+where
+    F: Float,
+{
+    #[contig(len)]
+    links: Dyn<[Link<F>]>,
+    gain: F,
+}
+
+#[contig(scalar = f64)]
+struct Marker {
+    value: f64,
+}
+
 #[contig(scalar = f64)]
-struct Generic<T> {
-    value: T,
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+fn robot_layout<F: Float>() -> RobotLayout<F> {
+    let cfg = RobotCfg::<F> {
+        links: DynArrayConfig {
+            len: 2,
+            elem: LinkCfg { mass: (), pos: () },
+        },
+        gain: (),
+    };
+    RobotLayout::from_config(&cfg)
 }
 
-fn main() {}
+fn main() {
+    let layout = robot_layout::<f64>();
+    assert_eq!(layout.len(), 9);
+    let mut buf = vec![0.0f64; layout.len()];
+    *layout.view(&mut buf).links().get_mut(1).mass() = 2.0;
+    assert_eq!(*layout.cview(&buf).links().get(1).mass(), 2.0);
+
+    let layout = robot_layout::<f32>();
+    let mut buf = vec![0.0f32; layout.len()];
+    *layout.view(&mut buf).gain() = 3.0;
+    assert_eq!(buf[8], 3.0);
+
+    assert_eq!(<Link<f32> as ContigStatic<f32>>::LEN, 4);
+    let mut buf = [0.0f32; 4];
+    *Link::<f32>::view_static_mut(&mut buf).mass() = 1.5;
+    assert_eq!(*Link::<f32>::view_static(&buf).mass(), 1.5);
+
+    // Layouts stay cloneable even though the marker type parameter is not `Clone`.
+    let layout = PairLayout::<Marker>::from_config(&PairCfg {
+        first: MarkerCfg { value: () },
+        second: MarkerCfg { value: () },
+    });
+    let copy = layout.clone();
+    assert_eq!(copy.off_second, 1..2);
+    assert_eq!(Pair::<Marker>::LEN, 2);
+}
@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Body {
+    #[contig(len)]
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Borrowed<'a> {
+    value: &'a f64,
+}
+
+fn main() {}
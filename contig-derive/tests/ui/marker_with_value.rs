@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Marker {
+    #[contig(len = 3)]
+    samples: contig_core::Dyn<[f64]>,
+}
+
+fn main() {}
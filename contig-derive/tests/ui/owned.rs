@@ -0,0 +1,130 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64, owned)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64, owned)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    #[contig(skip)]
+    name: String,
+    #[contig(rename = bias)]
+    imu_bias: Opt<[f64; 3]>,
+    #[contig(fixed_len = 2)]
+    gains: Dyn<[f64]>,
+    #[contig(default)]
+    time: f64,
+}
+
+#[contig(scalar = f64, owned)]
+struct Span(f64, #[contig(skip)] u8, f64);
+
+#[contig(scalar = F, owned)]
+struct Pair<F: Copy + 'static + ContigValue<F, Config = (), Layout = contig_core::ScalarLayout>> {
+    a: F,
+    b: [F; 2],
+}
+
+#[contig(scalar = f64, owned)]
+enum Segment {
+    Line { points: Dyn<[f64]> },
+    Hold,
+}
+
+#[contig(scalar = f64, owned)]
+struct Path {
+    #[contig(len)]
+    segments: Dyn<[Segment]>,
+}
+
+#[contig(scalar = F, owned)]
+enum Knot<F: Copy + 'static + ContigValue<F, Config = (), Layout = contig_core::ScalarLayout>> {
+    Fixed { at: F },
+    Free { at: F, slope: [F; 2] },
+}
+
+fn main() {
+    let value = RobotValue {
+        links: vec![
+            LinkValue { mass: 1.0, pos: [0.0, 0.5, 1.0] },
+            LinkValue { mass: 2.0, pos: [1.0, 1.5, 2.0] },
+        ],
+        bias: Some([0.1, 0.2, 0.3]),
+        gains: vec![4.0, 5.0],
+        time: 9.0,
+    };
+    let cfg = value.config_of();
+    assert_eq!(cfg.links.len, 2);
+    assert!(cfg.bias.is_present());
+
+    let layout = RobotLayout::from_config(&cfg);
+    let mut buffer = vec![0.0; layout.len()];
+    value.pack(&mut layout.view(&mut buffer));
+    assert_eq!(&buffer[..4], [1.0, 0.0, 0.5, 1.0]);
+    assert_eq!(buffer[layout.off_time.start], 9.0);
+    assert_eq!(RobotValue::unpack(&layout.cview(&buffer)), value);
+
+    let empty = RobotValue { links: Vec::new(), bias: None, ..value.clone() };
+    let robot = ContigBox::<f64, Robot>::from_value(&empty);
+    assert_eq!(robot.len(), 3);
+    assert_eq!(robot.to_value(), empty);
+
+    let span = SpanValue(1.0, 2.0);
+    let layout = SpanLayout::from_config(&span.config_of());
+    let mut buffer = [0.0; 2];
+    span.pack(&mut layout.view(&mut buffer));
+    assert_eq!(SpanValue::unpack(&layout.cview(&buffer)), span);
+
+    let pair = ContigBox::<f32, Pair<f32>>::from_value(&PairValue { a: 1.0, b: [2.0, 3.0] });
+    assert_eq!(pair.as_slice(), [1.0, 2.0, 3.0]);
+
+    let line = SegmentValue::Line { points: vec![1.0, 2.0] };
+    let segment = ContigBox::<f64, Segment>::from_value(&line);
+    assert_eq!(segment.as_slice(), [1.0, 2.0]);
+    assert_eq!(segment.to_value(), line);
+    assert!(matches!(SegmentValue::Hold {}.config_of(), SegmentCfg::Hold {}));
+
+    // Enum elements need no `Default` config, but must all share one.
+    let path = ContigBox::<f64, Path>::from_value(&PathValue { segments: Vec::new() });
+    assert!(path.is_empty());
+    let mixed = PathValue {
+        segments: vec![SegmentValue::Hold {}, line.clone(), line.clone()],
+    };
+    let err = <Path as ContigValue<f64>>::try_config_of(&mixed).unwrap_err();
+    assert_eq!(
+        err,
+        ContigError::MixedElements { path: "segments".into(), index: 1 }
+    );
+    let nested = PathValue {
+        segments: vec![
+            line.clone(),
+            SegmentValue::Line { points: vec![1.0] },
+        ],
+    };
+    let err = <Path as ContigValue<f64>>::try_config_of(&nested).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "array elements 0 and 1 of `segments` need different configs; use `Ragged` for \
+         per-element configs"
+    );
+    let points = vec![vec![1.0], vec![2.0, 3.0]];
+    let err = <Dyn<[Dyn<[f64]>]> as ContigValue<f64>>::try_config_of(&points).unwrap_err();
+    assert_eq!(err, ContigError::MixedElements { path: String::new(), index: 1 });
+    let ragged = <Dyn<[Ragged<[Segment]>]> as ContigValue<f64>>::try_config_of(&vec![
+        vec![line.clone()],
+        vec![SegmentValue::Line { points: vec![1.0, 2.0] }],
+    ]);
+    assert!(ragged.is_ok());
+
+    let knot = KnotValue::<f32>::Free { at: 1.0, slope: [2.0, 3.0] };
+    let boxed = ContigBox::<f32, Knot<f32>>::from_value(&knot);
+    assert_eq!(boxed.to_value(), knot.clone());
+    assert_ne!(knot, KnotValue::Fixed { at: 1.0 });
+    assert_eq!(format!("{:?}", KnotValue::<f32>::Fixed { at: 0.5 }), "Fixed { at: 0.5 }");
+    assert_eq!(format!("{:?}", span), "SpanValue(1.0, 2.0)");
+}
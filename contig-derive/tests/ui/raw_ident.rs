@@ -0,0 +1,25 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Raw {
+    r#type: f64,
+    r#match: [f64; 2],
+}
+
+#[contig(scalar = f64)]
+enum Kind {
+    Fixed { r#ref: f64 },
+}
+
+fn main() {
+    let layout = RawLayout::from_config(&RawCfg { r#type: (), r#match: () });
+    assert_eq!(layout.off_type, 0..1);
+    assert_eq!(layout.off_match, 1..3);
+    let mut buffer = [1.0, 2.0, 3.0];
+    assert_eq!(*layout.view(&mut buffer).r#type(), 1.0);
+    assert_eq!(layout.flat_names(), ["type", "match.0", "match.1"]);
+
+    let layout = KindLayout::from_config(&KindCfg::Fixed { r#ref: () });
+    assert_eq!(Kind::len(&layout), 1);
+}
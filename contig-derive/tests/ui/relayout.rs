@@ -0,0 +1,57 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    base: f64,
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    tail: f64,
+}
+
+#[contig(scalar = f64)]
+enum Segment {
+    Line { points: Dyn<[f64]> },
+    Hold,
+}
+
+fn main() {
+    let cfg = RobotCfg {
+        base: (),
+        links: DynArrayConfig { len: 1, elem: LinkCfg { mass: (), pos: () } },
+        tail: (),
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let buf = [9.0, 1.0, 2.0, 3.0, 4.0, 7.0];
+
+    let grown = RobotCfg {
+        links: DynArrayConfig { len: 2, ..cfg.links.clone() },
+        ..cfg.clone()
+    };
+    let (layout, buf) = layout.relayout(&buf, &grown);
+    assert_eq!(layout.len(), 10);
+    assert_eq!(buf, [9.0, 1.0, 2.0, 3.0, 4.0, 0.0, 0.0, 0.0, 0.0, 7.0]);
+
+    let mut robot = ContigBox::<f64, Robot>::from_parts(layout, buf).unwrap();
+    robot.relayout(&cfg);
+    assert_eq!(robot.as_slice(), [9.0, 1.0, 2.0, 3.0, 4.0, 7.0]);
+
+    let line = SegmentLayout::from_config(&SegmentCfg::Line {
+        points: DynArrayConfig { len: 2, elem: () },
+    });
+    let (_, buf) = line.relayout(
+        &[1.0, 2.0],
+        &SegmentCfg::Line {
+            points: DynArrayConfig { len: 3, elem: () },
+        },
+    );
+    assert_eq!(buf, [1.0, 2.0, 0.0]);
+    let (hold, buf) = line.relayout(&[1.0, 2.0], &SegmentCfg::Hold {});
+    assert_eq!((hold.len(), buf.len()), (0, 0));
+}
@@ -0,0 +1,110 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    #[contig(rename = com)]
+    center: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute { angle: f64 },
+    Locked { angle: f64 },
+    Fixed,
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    #[contig(len)]
+    joints: Dyn<[Joint]>,
+}
+
+fn main() {
+    let cfg = RobotCfg {
+        links: DynArrayConfig {
+            len: 2,
+            elem: LinkCfg { mass: (), com: () },
+        },
+        joints: DynArrayConfig {
+            len: 1,
+            elem: JointCfg::Revolute { angle: () },
+        },
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let schema = layout.schema();
+    assert_eq!(layout.schema_hash(), Robot::schema_hash(&layout));
+    assert_eq!(layout.schema_hash(), RobotLayout::from_config(&cfg.clone()).schema_hash());
+    let mut shorter = cfg.clone();
+    shorter.links.len = 1;
+    assert_ne!(layout.schema_hash(), RobotLayout::from_config(&shorter).schema_hash());
+    assert_eq!(schema.kind, SchemaKind::Struct);
+    assert_eq!(schema.range, 0..9);
+
+    let links = &schema.children[0];
+    assert_eq!(links.segment, Some(Segment::Field("links")));
+    let com = &links.children[1].children[1];
+    assert_eq!(com.segment, Some(Segment::Field("com")));
+    assert_eq!(com.range, 5..8);
+    assert_eq!(com.children[2].kind, SchemaKind::Scalar);
+
+    let joint = &schema.children[1].children[0];
+    assert_eq!(joint.kind, SchemaKind::Enum { variant: "Revolute" });
+    assert_eq!(joint.children[0].range, 8..9);
+
+    assert_eq!(layout.path_of(6).as_deref(), Some("links[1].com[1]"));
+    assert_eq!(layout.path_of(8).as_deref(), Some("joints[0].Revolute.angle"));
+    assert_eq!(layout.path_of(9), None);
+    assert_eq!(
+        <Robot as Contig<f64>>::path_of(&layout, 0).as_deref(),
+        Some("links[0].mass")
+    );
+
+    assert_eq!(layout.range_of("links[1].com"), Ok(5..8));
+    assert_eq!(layout.range_of("joints[0].Revolute.angle"), Ok(8..9));
+    assert_eq!(layout.range_of("joints[0].Revolute"), Ok(8..9));
+    assert!(layout.range_of("joints[0].angle").is_err());
+    assert!(layout.range_of("joints[0].Locked.angle").is_err());
+    assert!(layout.range_of("links[1].center").is_err());
+    let mut buf = [0.0; 9];
+    layout.get_path_mut(&mut buf, "links[1].com")
+        .unwrap()
+        .copy_from_slice(&[1.0, 2.0, 3.0]);
+    assert_eq!(layout.get_path(&buf, "links[1].com[2]"), Ok(&[3.0][..]));
+    assert_eq!(*layout.cview(&buf).links().get(1).com().get(1), 2.0);
+
+    assert_eq!(
+        layout.flat_names(),
+        [
+            "links.0.mass",
+            "links.0.com.0",
+            "links.0.com.1",
+            "links.0.com.2",
+            "links.1.mass",
+            "links.1.com.0",
+            "links.1.com.1",
+            "links.1.com.2",
+            "joints.0.Revolute.angle",
+        ]
+    );
+
+    // Switching a joint's variant must not carry its fields across.
+    let revolute = JointLayout::from_config(&JointCfg::Revolute { angle: () });
+    let (_, buf, report) =
+        migrate::<f64, Joint>(&revolute.flat_names(), &[1.0], &JointCfg::Locked { angle: () });
+    assert_eq!(buf, [0.0]);
+    assert_eq!(report.kept, 0);
+    assert_eq!(report.added, ["Locked"]);
+    assert_eq!(report.removed, ["Revolute"]);
+
+    let nested = <Robot as Contig<f64>>::schema(&layout, 10);
+    assert_eq!(nested.children[1].range, 18..19);
+    assert!(
+        JointLayout::from_config(&JointCfg::Fixed {})
+            .schema()
+            .is_leaf()
+    );
+}
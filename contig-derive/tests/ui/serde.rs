@@ -0,0 +1,46 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    time: f64,
+}
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute { angle: f64 },
+    Prismatic { offset: [f64; 2] },
+}
+
+fn main() {
+    let cfg: RobotCfg = serde_json::from_str(
+        r#"{"links":{"len":1,"elem":{"mass":null,"pos":null}},"time":null}"#,
+    )
+    .unwrap();
+    let layout = RobotLayout::from_config(&cfg);
+    let json = serde_json::to_string(&layout).unwrap();
+    assert_eq!(serde_json::from_str::<RobotLayout>(&json).unwrap(), layout);
+
+    let mut buffer: Vec<f64> = (0..layout.len()).map(|i| i as f64).collect();
+    let json = serde_json::to_string(&layout.cview(&buffer)).unwrap();
+    assert_eq!(json, r#"{"links":[{"mass":0.0,"pos":[1.0,2.0,3.0]}],"time":4.0}"#);
+    buffer.fill(0.0);
+    let mut de = serde_json::Deserializer::from_str(&json);
+    layout.view(&mut buffer).deserialize_from(&mut de).unwrap();
+    assert_eq!(buffer, [0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    let joint = JointLayout::from_config(&JointCfg::Prismatic { offset: () });
+    let json = serde_json::to_string(&joint).unwrap();
+    assert_eq!(serde_json::from_str::<JointLayout>(&json).unwrap(), joint);
+    let buffer = [5.0, 6.0];
+    let json = serde_json::to_string(&joint.cview(&buffer)).unwrap();
+    assert_eq!(json, r#"{"Prismatic":{"offset":[5.0,6.0]}}"#);
+}
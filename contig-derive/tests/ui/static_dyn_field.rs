@@ -0,0 +1,12 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Runtime {
+    #[contig(len)]
+    values: Dyn<[f64]>,
+}
+
+fn main() {
+    let _ = [0.0f64; Runtime::LEN];
+}
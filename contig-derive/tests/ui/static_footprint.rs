@@ -0,0 +1,41 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Chain {
+    base: Link,
+    links: [Link; 2],
+}
+
+fn main() {
+    assert_eq!(Link::LEN, 4);
+    assert_eq!(Chain::LEN, 12);
+
+    let runtime = ChainLayout::from_config(&ChainCfg {
+        base: LinkCfg { mass: (), pos: () },
+        links: LinkCfg { mass: (), pos: () },
+    });
+    assert_eq!(runtime.off_links, Chain::LAYOUT.off_links);
+    assert_eq!(runtime.len(), Chain::LEN);
+
+    let mut buf = [0.0f64; Chain::LEN];
+    {
+        let mut view = Chain::view_static_mut(&mut buf);
+        *view.base().mass() = 1.0;
+        let mut links = view.links();
+        *links.get_mut(1).mass() = 2.0;
+        *links.get_mut(1).pos().get_mut(2) = 3.0;
+    }
+    assert_eq!(buf[0], 1.0);
+    assert_eq!(buf[8], 2.0);
+    assert_eq!(buf[11], 3.0);
+
+    let view = Chain::view_static(&buf);
+    assert_eq!(*view.links().get(1).mass(), 2.0);
+}
@@ -0,0 +1,47 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Mass(f64);
+
+#[contig(scalar = f64)]
+struct Span(#[contig(rename = start)] f64, #[contig(len)] Dyn<[f64]>);
+
+#[contig(scalar = f64)]
+struct Placeholder;
+
+#[contig(scalar = f64)]
+struct Holder {
+    mass: Mass,
+    marker: Placeholder,
+    span: Span,
+}
+
+fn main() {
+    let layout = MassLayout::from_config(&MassCfg(()));
+    assert_eq!(layout.len(), 1);
+    let mut buf = [0.0f64; 1];
+    *layout.view(&mut buf)._0() = 2.5;
+    assert_eq!(*layout.cview(&buf)._0(), 2.5);
+
+    let empty = PlaceholderLayout::from_config(&PlaceholderCfg);
+    assert!(empty.is_empty());
+    assert_eq!(<Placeholder as ContigStatic<f64>>::LEN, 0);
+
+    let cfg = HolderCfg {
+        mass: MassCfg(()),
+        marker: PlaceholderCfg,
+        span: SpanCfg((), DynArrayConfig { len: 2, elem: () }),
+    };
+    let layout = HolderLayout::from_config(&cfg);
+    assert_eq!(layout.len(), 4);
+
+    let mut buf = [0.0f64; 4];
+    {
+        let mut view = layout.view(&mut buf);
+        let mut span = view.span();
+        *span.start() = 1.0;
+        *span._1().get_mut(1) = 3.0;
+    }
+    assert_eq!(buf, [0.0, 1.0, 0.0, 3.0]);
+}
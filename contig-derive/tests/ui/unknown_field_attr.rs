@@ -0,0 +1,9 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Typo {
+    #[contig(lenn)]
+    samples: contig_core::Dyn<[f64]>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use contig_derive::contig;
+
+#[contig(scalar = f64, layout = Packed)]
+struct Link {
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+use contig_core::prelude::*;
+use contig_derive::contig;
+
+#[contig(scalar = f64)]
+struct Link {
+    mass: f64,
+    pos: [f64; 3],
+}
+
+#[contig(scalar = f64)]
+struct Robot {
+    #[contig(len)]
+    links: Dyn<[Link]>,
+    time: f64,
+}
+
+#[contig(scalar = f64)]
+enum Joint {
+    Revolute { angle: f64 },
+    Prismatic { offset: [f64; 2] },
+}
+
+#[contig(scalar = F)]
+struct Pair<F: Real + Contig<F, Config = (), Layout = contig_core::ScalarLayout>> {
+    a: F,
+    b: F,
+}
+
+fn main() {
+    let cfg = RobotCfg {
+        links: DynArrayConfig { len: 2, elem: LinkCfg { mass: (), pos: () } },
+        time: (),
+    };
+    let layout = RobotLayout::from_config(&cfg);
+    let x0 = vec![1.0; layout.len()];
+    let dx = vec![2.0; layout.len()];
+    let mut x = vec![0.0; layout.len()];
+
+    // Line search restricted to the links: x.links = x0.links + alpha * dx.links.
+    let mut view = layout.view(&mut x);
+    view.links().copy_from(&layout.cview(&x0).links());
+    view.links().axpy(0.5, &layout.cview(&dx).links());
+    assert_eq!(*view.time(), 0.0);
+    assert_eq!(view.links().dot(&layout.cview(&dx).links()), 32.0);
+
+    // Whole-struct operations stop at the layout's end even on a longer buffer.
+    let mut padded = vec![1.0; layout.len() + 1];
+    let mut view = layout.view(&mut padded);
+    view.scale(3.0);
+    assert_eq!(view.dot(&layout.cview(&x0)), 27.0);
+    view.fill(0.0);
+    assert_eq!(layout.cview(&padded).norm(), 0.0);
+    assert_eq!(padded[layout.len()], 1.0);
+
+    // Enum views have no arithmetic methods; the slice kernels cover the same range.
+    let joint = JointLayout::from_config(&JointCfg::Prismatic { offset: () });
+    let mut buffer = [3.0, 4.0, 1.0];
+    contig_core::ops::scale(2.0, &mut buffer[..joint.len()]);
+    assert_eq!(contig_core::ops::norm(&buffer[..joint.len()]), 10.0);
+    assert_eq!(buffer[joint.len()], 1.0);
+
+    let pair = PairLayout::<f32>::from_config(&PairCfg { a: (), b: () });
+    let mut buffer = [3.0f32, 4.0];
+    assert_eq!(pair.view(&mut buffer).norm(), 5.0);
+}
@@ -1,6 +1,6 @@
 use contig_derive::contig;
 
 #[contig(scalar = f64)]
-struct WrongAttr(f64);
+struct WrongAttr(#[contig(rename = "mass")] f64);
 
 fn main() {}